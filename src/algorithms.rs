@@ -1,14 +1,19 @@
 use std::collections::BTreeSet;
+use fxhash::FxHashSet;
 
 use graphbench::{graph::*, iterators::LeftNeighIterable};
 use graphbench::degengraph::DegenGraph;
 
-use crate::nquery::NQuery;
+use crate::nquery::{NQuery, Orientation};
 
 use itertools::*;
+use rand::prelude::*;
 use crate::skipcombs::{SkippableCombinations, SkippableCombinationsIter};
 
 fn binom(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
     let mut res = 1;
     for i in 0..k {
         res = (res * (n - i)) / (i + 1);
@@ -46,8 +51,10 @@ pub struct VCAlgorithm<'a> {
     local_lower_bound:VertexMap<u8>,
     local_upper_bound:VertexMap<u8>,
     shatter_candidates:VertexSet,
+    base_candidates:VertexSet,
     cover_candidates:VertexSet,
     vc_dim:usize,
+    upper_bound:usize,
     d: usize,
     logd: f32
 }
@@ -65,19 +72,194 @@ impl<'a> VCAlgorithm<'a> {
 
         let vc_dim = 1;
         let mut nquery = NQuery::new(graph);
-        VCAlgorithm{ graph, d, logd, shatter_candidates, cover_candidates, nquery, local_lower_bound, local_upper_bound, vc_dim}
+        let base_candidates = shatter_candidates.clone();
+        let upper_bound = d+1;
+        VCAlgorithm{ graph, d, logd, shatter_candidates, base_candidates, cover_candidates, nquery, local_lower_bound, local_upper_bound, vc_dim, upper_bound}
     }
 
     pub fn set_shatter_candidates(&mut self, candidates:&VertexSet) {
         self.shatter_candidates = candidates.iter().filter(|x| self.graph.contains(x)).cloned().collect();
+        self.base_candidates = self.shatter_candidates.clone();
+    }
+
+    /// Only vertices in `witnesses` may realise traces, i.e. we compute the VC dimension of
+    /// the set system {N(v) : v ∈ witnesses}.
+    pub fn set_witness_candidates(&mut self, witnesses:&VertexSet) {
+        self.nquery.set_witness_candidates(witnesses);
+    }
+
+    /// Computes the VC dimension of the out- or in-neighbourhoods of a directed graph. The graph passed
+    /// to `new` must be the underlying undirected graph of `arcs`.
+    pub fn set_arcs(&mut self, arcs:&FxHashSet<(Vertex, Vertex)>, orientation:Orientation) {
+        self.nquery.set_arcs(arcs, orientation);
+    }
+
+    /// Computes the VC dimension of the set system {N(v) ∩ ground : v ∈ sets}, e.g. for the two
+    /// sides of a bipartite graph. Only vertices of `ground` can be shattered and only vertices
+    /// of `sets` act as witnesses.
+    pub fn set_sides(&mut self, ground:&VertexSet, sets:&VertexSet) {
+        self.set_shatter_candidates(ground);
+        self.set_witness_candidates(sets);
+    }
+
+    /// Compute the VC dimension of the set system of closed neighbourhoods. Must be called before `set_radius`.
+    pub fn set_closed(&mut self, closed:bool) {
+        self.nquery.set_closed(closed);
+        self.d = self.nquery.max_left_size();
+        self.logd = (self.d as f32).log2();
+    }
+
+    /// Compute the VC dimension of the set system of balls of radius `radius`. For radius
+    /// larger than one the result is only a lower bound since the covering argument
+    /// which makes the search exhaustive only applies to neighbourhoods.
+    pub fn set_radius(&mut self, radius:usize) {
+        self.nquery.set_radius(radius);
+        if radius > 1 {
+            // Saturate instead of wrapping, a shattered set never has more than 255 elements
+            self.local_upper_bound = self.graph.vertices()
+                .map(|v| (*v, std::cmp::min(self.nquery.left_ball(v).len(), u8::MAX as usize) as u8))
+                .collect();
+            self.d = self.nquery.max_left_ball() - 1;
+            self.logd = (self.d as f32).log2();
+        }
+    }
+
+    pub fn vc_dim(&self) -> usize {
+        self.vc_dim
+    }
+
+    /// Computes combinatorial upper bounds on the VC dimension which hold before any search:
+    /// a shattered set of size k needs 2^k sets, and each of its k elements must dominate the 
+    /// degree profile of size k. The latter condition is monotone in k. For radius one we also
+    /// have the bound d+1.
+    fn compute_upper_bound(&mut self) -> usize {
+        let witnesses = self.nquery.num_witnesses();
+        let log_bound = if witnesses == 0 { 0 } else { (witnesses as f64).log2().floor() as usize };
+
+        let mut upper = log_bound;
+        if self.nquery.radius() == 1 {
+            upper = std::cmp::min(upper, self.d+1);
+        }
+
+        let profiles = self.base_candidates.iter().map(|v| self.nquery.degree_profile(v)).collect_vec();
+        let mut profile_bound = 0;
+        while profile_bound < upper {
+            let k = profile_bound+1;
+            let degree_profile = generate_degree_profile(k);
+            let dominating = profiles.iter().filter(|degrees| dominates_profile(degrees, &degree_profile)).count();
+            if dominating < k {
+                break;
+            }
+            profile_bound = k;
+        }
+
+        if self.nquery.radius() == 1 {
+            println!("Trivial upper bounds: d+1 = {}, log2 of number of sets = {log_bound}", self.d+1);
+        } else {
+            println!("Trivial upper bound: log2 of number of sets = {log_bound}");
+        }
+
+        if profile_bound < upper {
+            println!("VC dimension is at most {profile_bound} (degree profiles)");
+        } else {
+            println!("VC dimension is at most {profile_bound}");
+        }
+        profile_bound
+    }
+
+    /// Returns those shatter candidates whose neighbours' degrees dominate the 
+    /// degree profile of a shattered set of size `k`.
+    fn candidates_for_size(&self, k:usize) -> VertexSet {
+        let degree_profile = generate_degree_profile(k);
+        self.base_candidates.iter().filter(|v| {
+            let degrees = self.nquery.degree_profile(v);
+            dominates_profile(&degrees, &degree_profile)
+        }).cloned().collect()
+    }
+
+    /// Returns the length of the shortest prefix of `S` which is not shattered,
+    /// only prefixes of length 2 up to |S|-1 are tested.
+    fn unshattered_prefix(&self, S:&[Vertex]) -> Option<usize> {
+        (2..S.len()).find(|&k| !self.nquery.is_shattered(&S[..k]))
+    }
+
+    /// Lists all shattered sets of size `vc_dim`, at most `limit` many. This method is 
+    /// intended to be called after `run` has determined the VC dimension. Returns the number
+    /// of sets found.
+    pub fn enumerate_witnesses(&mut self, limit:Option<usize>) -> usize {
+        let k = self.vc_dim;
+        let limit = limit.unwrap_or(usize::MAX);
+        let candidates = self.candidates_for_size(k);
+        self.nquery.ensure_size_restricted(k, &candidates);
+
+        // Every shattered set is contained in the set of the vertex which realises the full trace,
+        // so we either test all candidate sets or all those which lie in a set of the set system.
+        let brute_force_estimate = binom(candidates.len(), k);
+        let neighbourhood_estimate:usize = self.graph.vertices()
+            .map(|w| binom(self.nquery.set_of(w).iter().filter(|u| candidates.contains(u)).count(), k))
+            .sum();
+
+        let mut count = 0;
+        if brute_force_estimate <= neighbourhood_estimate {
+            println!("Enumerating witnesses by brute-force: ({} choose {k}) candidates", candidates.len());
+            let candidates:Vec<Vertex> = candidates.into_iter().sorted_unstable().collect();
+            let mut it = candidates.into_iter().combinations_skippable(k);
+            while let Some(S) = it.next() {
+                if let Some(prefix) = self.unshattered_prefix(&S) {
+                    it.skip_prefix(prefix);
+                    continue;
+                }
+
+                if self.nquery.is_shattered(&S) {
+                    count += 1;
+                    println!("Shattered set #{count}: {S:?}");
+                    if count >= limit {
+                        break;
+                    }
+                }
+            }
+        } else {
+            println!("Enumerating witnesses in neighbourhoods: {neighbourhood_estimate} candidate sets");
+            // Sets are found in multiple neighbourhoods, so we keep track of what we already reported
+            let mut seen:BTreeSet<Vec<Vertex>> = BTreeSet::default();
+            'outer: for w in self.graph.vertices() {
+                let N:Vec<Vertex> = self.nquery.set_of(w).into_iter().filter(|u| candidates.contains(u)).sorted_unstable().collect();
+
+                let mut it = N.into_iter().combinations_skippable(k);
+                while let Some(S) = it.next() {
+                    if seen.contains(&S) {
+                        continue;
+                    }
+
+                    if let Some(prefix) = self.unshattered_prefix(&S) {
+                        it.skip_prefix(prefix);
+                        continue;
+                    }
+
+                    if self.nquery.is_shattered(&S) {
+                        count += 1;
+                        println!("Shattered set #{count}: {S:?}");
+                        seen.insert(S);
+                        if count >= limit {
+                            break 'outer;
+                        }
+                    }
+                }
+            }
+        }
+
+        println!("Found {count} shattered sets of size {k}");
+        count
     }
 
     pub fn run(&mut self) {
         let mut improved = true;
         let mut cover_size = 1;
 
+        self.upper_bound = std::cmp::max(self.compute_upper_bound(), self.vc_dim);
+
         // Main loop: try to find larger and larger shattered sets
-        while improved && self.vc_dim <= self.d+1 {
+        while improved && self.vc_dim < self.upper_bound {
             improved = false;
 
             let brute_force_estimate = binom(self.shatter_candidates.len(), self.vc_dim+1);
@@ -126,8 +308,7 @@ impl<'a> VCAlgorithm<'a> {
 
                     // Collect candidate set
                     let mut N:VertexSet = VertexSet::default();
-                    N.insert(*c);
-                    N.extend( self.graph.left_neighbours_slice(c));
+                    N.extend( self.nquery.left_ball(c));
 
                     // Retain only those elements that are witness candidates
                     N.retain(|x| self.shatter_candidates.contains(x) );
@@ -192,7 +373,7 @@ impl<'a> VCAlgorithm<'a> {
                     // Collect candidate set
                     let mut N:VertexSet = C.iter().map(|u| *u).collect();
                     for &u in &C {
-                        N.extend( self.graph.left_neighbours_slice(&u));
+                        N.extend( self.nquery.left_ball(&u));
                     }
 
                     // Retain only those elements that are witness candidates
@@ -242,7 +423,190 @@ impl<'a> VCAlgorithm<'a> {
             }
         }
 
-        println!("Largest shattered set: {:?}", self.vc_dim);
+        if self.nquery.radius() == 1 {
+            println!("Largest shattered set: {:?}", self.vc_dim);
+        } else {
+            println!("Largest shattered set found: {:?} (lower bound for radius {})", self.vc_dim, self.nquery.radius());
+        }
+    }
+
+    /// Returns the sorted candidates for shattered sets of size `k` and makes sure that
+    /// the query structure can answer queries of that size.
+    fn prepare_size(&mut self, k:usize) -> Vec<Vertex> {
+        let candidates = self.candidates_for_size(k);
+        self.nquery.ensure_size_restricted(k, &candidates);
+        candidates.into_iter().sorted_unstable().collect()
+    }
+
+    /// Computes the number of shattered sets of every size. Sizes up to `exact_up_to` are
+    /// counted exactly, larger sizes are estimated from `samples` random walks.
+    ///
+    /// Since shattered sets are closed under taking subsets, they form a tree in which the 
+    /// children of a set T are the shattered sets T + x with x larger than all elements of T. The 
+    /// estimate is Knuth's tree-size estimator started at a uniformly random shattered set of
+    /// size `exact_up_to`: the product of the branching degrees along a random root-leaf path 
+    /// is an unbiased estimate of the number of nodes at every depth.
+    pub fn shatter_profile(&mut self, exact_up_to:usize, samples:usize) -> Vec<f64> {
+        let mut profile:Vec<f64> = vec![1.0]; // The empty set is always shattered
+        let mut errors:Vec<f64> = vec![0.0];
+        let mut level:Vec<Vec<Vertex>> = vec![vec![]];
+
+        for k in 1..=exact_up_to {
+            let candidates = self.prepare_size(k);
+            println!("Counting shattered sets of size {k}: ({} choose {k}) candidates", candidates.len());
+
+            let mut count = 0;
+            let mut next_level = Vec::default();
+            let mut it = candidates.into_iter().combinations_skippable(k);
+            while let Some(S) = it.next() {
+                if let Some(prefix) = self.unshattered_prefix(&S) {
+                    it.skip_prefix(prefix);
+                    continue;
+                }
+
+                if self.nquery.is_shattered(&S) {
+                    count += 1;
+                    if k == exact_up_to {
+                        next_level.push(S);
+                    }
+                }
+            }
+
+            println!("  > {count} shattered sets of size {k}");
+            profile.push(count as f64);
+            errors.push(0.0);
+            if count == 0 {
+                break;
+            }
+            level = next_level;
+        }
+
+        if profile.len() == exact_up_to+1 && *profile.last().unwrap() > 0.0 && samples > 0 {
+            println!("Estimating number of larger shattered sets from {samples} samples");
+            let mut rng = rand::thread_rng();
+            let mut candidates:Vec<Vec<Vertex>> = Vec::default();
+            let mut sums:Vec<f64> = Vec::default();
+            let mut squares:Vec<f64> = Vec::default();
+
+            for _ in 0..samples {
+                let mut S = level.choose(&mut rng).unwrap().clone();
+                let mut weight = level.len() as f64;
+                let mut depth = S.len();
+            
+                loop {
+                    while candidates.len() <= depth+1 {
+                        let size = candidates.len();
+                        candidates.push(self.prepare_size(size));
+                    }
+
+                    let last = S.last().cloned();
+                    let children = candidates[depth+1].iter()
+                        .filter(|x| last.is_none_or(|u| **x > u))
+                        .filter(|x| {
+                            let mut T = S.clone();
+                            T.push(**x);
+                            self.nquery.is_shattered(&T)
+                        })
+                        .cloned().collect_vec();
+
+                    if children.is_empty() {
+                        break;
+                    }
+
+                    weight *= children.len() as f64;
+                    S.push(*children.choose(&mut rng).unwrap());
+                    depth += 1;
+
+                    let ix = depth - exact_up_to - 1;
+                    if sums.len() <= ix {
+                        sums.push(0.0);
+                        squares.push(0.0);
+                    }
+                    sums[ix] += weight;
+                    squares[ix] += weight*weight;
+                }
+            }
+
+            let samples = samples as f64;
+            for (sum, square) in sums.iter().zip(squares.iter()) {
+                let mean = sum / samples;
+                let variance = (square / samples - mean*mean).max(0.0);
+                profile.push(mean);
+                errors.push((variance / samples).sqrt());
+            }
+        }
+
+        println!("k\tshattered\tstderr");
+        for (k, (count, error)) in profile.iter().zip(errors.iter()).enumerate().skip(1) {
+            if k <= exact_up_to {
+                println!("{k}\t{count}\t(exact)");
+            } else {
+                println!("{k}\t{count:.1}\t{error:.1}");
+            }
+        }
+
+        profile
+    }
+
+    /// Computes for every vertex v the size of the largest shattered set contained in
+    /// v's closed left neighbourhood or, if `ball` is set, in v's closed neighbourhood.
+    pub fn local_vc_dimensions(&mut self, ball:bool) -> VertexMap<usize> {
+        let mut levels:Vec<VertexSet> = Vec::default();
+        let mut res = VertexMap::default();
+
+        let vertices = self.graph.vertices().cloned().collect_vec();
+        for v in vertices {
+            let mut N:Vec<Vertex> = if ball {
+                self.nquery.ball(&v).into_iter().collect()
+            } else {
+                self.nquery.left_ball(&v)
+            };
+            N.sort_unstable();
+
+            let mut local_vc = 0;
+            loop {
+                let k = local_vc + 1;
+                while levels.len() <= k {
+                    let size = levels.len();
+                    let candidates = self.candidates_for_size(size);
+                    self.nquery.ensure_size_restricted(size, &candidates);
+                    levels.push(candidates);
+                }
+
+                let cands = N.iter().filter(|u| levels[k].contains(u)).cloned().collect_vec();
+                let mut found = false;
+                let mut it = cands.into_iter().combinations_skippable(k);
+                while let Some(S) = it.next() {
+                    if let Some(prefix) = self.unshattered_prefix(&S) {
+                        it.skip_prefix(prefix);
+                        continue;
+                    }
+
+                    if self.nquery.is_shattered(&S) {
+                        found = true;
+                        break;
+                    }
+                }
+
+                if !found {
+                    break;
+                }
+                local_vc = k;
+            }
+
+            if !ball {
+                // The search was exhaustive, so both local bounds are tight
+                self.local_lower_bound.insert(v, local_vc as u8);
+                self.local_upper_bound.insert(v, local_vc as u8);
+            }
+            res.insert(v, local_vc);
+        }
+
+        let max_vc = res.values().max().cloned().unwrap_or(0);
+        let num_max = res.values().filter(|x| **x == max_vc).count();
+        println!("Largest local VC dimension is {max_vc}, attained by {num_max} vertices");
+
+        res
     }
 
     fn recompute_candidates(&mut self) {
@@ -261,9 +625,8 @@ impl<'a> VCAlgorithm<'a> {
         self.cover_candidates.retain(|v| {
             let mut covers = false;
 
-            let mut num_cands = self.graph.left_neighbours_slice(v).iter()
+            let num_cands = self.nquery.left_ball(v).iter()
                 .filter(|u| self.shatter_candidates.contains(u)).count();
-            num_cands += self.shatter_candidates.contains(v) as usize;
 
             // Update local upper bound
             self.local_upper_bound.entry(*v).and_modify(|e| *e = std::cmp::min(*e, num_cands as u8));
@@ -279,11 +642,51 @@ impl<'a> VCAlgorithm<'a> {
 
 
 
+/// Computes the dual VC dimension of the set system {N(x) : x ∈ sets}. The dual set system has 
+/// ground set `sets` and contains for every vertex v the set {x ∈ sets : v ∈ N(x)} = N(v) ∩ sets,
+/// so we look for the largest subset of `sets` which is shattered by the neighbourhoods of all
+/// vertices. Since neighbourhoods are symmetric, the dual VC dimension equals the VC dimension 
+/// if `sets` contains all vertices.
+pub struct DualVCAlgorithm<'a> {
+    vc: VCAlgorithm<'a>
+}
+
+impl<'a> DualVCAlgorithm<'a> {
+    pub fn new(graph: &'a DegenGraph, sets:&VertexSet) -> Self {
+        let mut vc = VCAlgorithm::new(graph);
+        vc.set_shatter_candidates(sets);
+        Self{ vc }
+    }
+
+    /// Only the neighbourhoods of vertices in `witnesses` form sets of the dual set system.
+    pub fn set_witness_candidates(&mut self, witnesses:&VertexSet) {
+        self.vc.set_witness_candidates(witnesses);
+    }
+
+    pub fn set_closed(&mut self, closed:bool) {
+        self.vc.set_closed(closed);
+    }
+
+    pub fn set_radius(&mut self, radius:usize) {
+        self.vc.set_radius(radius);
+    }
+
+    pub fn vc_dim(&self) -> usize {
+        self.vc.vc_dim()
+    }
+
+    pub fn run(&mut self) {
+        self.vc.run();
+    }
+}
+
+
 pub struct LadderAlgorithm<'a> {
     graph: &'a DegenGraph,
     nquery: NQuery<'a>,
     ladder_lower:usize,
     ladder_upper:usize,
+    candidates:Option<VertexSet>,
     d: usize,
 }
 
@@ -294,19 +697,64 @@ impl<'a> LadderAlgorithm<'a> {
         let ladder_lower = 1;
         let ladder_upper = 2*d+1;
         let mut nquery = NQuery::new(graph);
-        Self{ graph, d, nquery, ladder_lower, ladder_upper}
+        Self{ graph, d, nquery, ladder_lower, ladder_upper, candidates: None}
+    }
+
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.ladder_lower, self.ladder_upper)
+    }
+
+    /// Use closed neighbourhoods. Must be called before `set_radius`.
+    pub fn set_closed(&mut self, closed:bool) {
+        self.nquery.set_closed(closed);
+        self.d = self.nquery.max_left_size();
+        self.ladder_upper = 2*self.d+1;
+    }
+
+    /// Only search for ladders whose elements are contained in `candidates`, witnesses can be any vertex.
+    /// The improved upper bound after the search relies on finding the witness side of a ladder inside a 
+    /// left neighbourhood, which need not consist of candidates, hence only the degeneracy bound remains.
+    pub fn set_candidates(&mut self, candidates:&VertexSet) {
+        let candidates:VertexSet = candidates.iter().filter(|x| self.graph.contains(x)).cloned().collect();
+        // A ladder of size one is a candidate contained in some set
+        self.ladder_lower = usize::from(self.graph.vertices().any(|w| self.nquery.set_of(w).iter().any(|v| candidates.contains(v))));
+        self.candidates = Some(candidates);
+    }
+
+    /// Only search for ladders between the two sides of a bipartite graph, see `VCAlgorithm::set_sides`.
+    /// The elements of the ladder lie in `ground` and its witnesses in `sets`. As with `set_candidates` 
+    /// only the degeneracy bound remains as upper bound.
+    pub fn set_sides(&mut self, ground:&VertexSet, sets:&VertexSet) {
+        self.nquery.set_witness_candidates(sets);
+        self.set_candidates(ground);
+    }
+
+    /// Use the set system of balls of radius `radius` instead of neighbourhoods. For radius larger
+    /// than one only lower bounds are reported, the upper bounds derived from the degeneracy do not apply.
+    pub fn set_radius(&mut self, radius:usize) {
+        self.nquery.set_radius(radius);
+        if radius > 1 {
+            self.ladder_upper = self.nquery.max_left_ball();
+        }
     }
 
     pub fn run(&mut self) {
-        println!("Ladder index is at most {}", self.ladder_upper);
+        if self.nquery.radius() == 1 {
+            println!("Ladder index is at most {}", self.ladder_upper);
+        }
 
         let start = self.ladder_lower+1;
         let end = self.ladder_upper;
         'outer: for k in start..=end {
-            self.nquery.ensure_size(k);
+            match &self.candidates {
+                Some(candidates) => self.nquery.ensure_size_restricted(k, candidates),
+                None => self.nquery.ensure_size(k)
+            }
             for v in self.graph.vertices() {
-                let mut N = self.graph.left_neighbours(v);
-                N.push(*v);
+                let mut N = self.nquery.left_ball(v);
+                if let Some(candidates) = &self.candidates {
+                    N.retain(|x| candidates.contains(x));
+                }
 
                 for S in N.into_iter().combinations(k) {
                     if self.nquery.contains_ladder(&S) {
@@ -320,11 +768,98 @@ impl<'a> LadderAlgorithm<'a> {
                 }
             }
  
-            self.ladder_upper = std::cmp::min(2*self.ladder_lower + 1, self.ladder_upper);
+            if self.nquery.radius() == 1 && self.candidates.is_none() {
+                self.ladder_upper = std::cmp::min(2*self.ladder_lower + 1, self.ladder_upper);
+            }
+            break;
+        }
+
+        if self.nquery.radius() == 1 {
+            println!("Ladder index is at most {}", self.ladder_upper);
+        } else {
+            println!("No upper bound on the ladder index for radius {}", self.nquery.radius());
+        }
+    }
+}
+
+
+pub struct SemiLadderAlgorithm<'a> {
+    graph: &'a DegenGraph,
+    nquery: NQuery<'a>,
+    semi_ladder_lower:usize,
+    semi_ladder_upper:usize,
+    d: usize,
+}
+
+impl<'a> SemiLadderAlgorithm<'a> {
+    pub fn new(graph: &'a DegenGraph) -> Self {
+        let d = *graph.left_degrees().values().max().unwrap() as usize;
+
+        // A semi-ladder of length k contains k(k-1)/2 edges between its 2k vertices,
+        // a d-degenerate graph on 2k vertices has at most 2dk edges.
+        let semi_ladder_lower = 1;
+        let semi_ladder_upper = 4*d+1;
+        let mut nquery = NQuery::new(graph);
+        Self{ graph, d, nquery, semi_ladder_lower, semi_ladder_upper}
+    }
+
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.semi_ladder_lower, self.semi_ladder_upper)
+    }
+
+    /// Use closed neighbourhoods, see `LadderAlgorithm::set_closed`.
+    pub fn set_closed(&mut self, closed:bool) {
+        self.nquery.set_closed(closed);
+        self.d = self.nquery.max_left_size();
+        self.semi_ladder_upper = 4*self.d+1;
+    }
+
+    /// See `LadderAlgorithm::set_radius`.
+    pub fn set_radius(&mut self, radius:usize) {
+        self.nquery.set_radius(radius);
+        if radius > 1 {
+            self.semi_ladder_upper = self.nquery.max_left_ball();
+        }
+    }
+
+    pub fn run(&mut self) {
+        if self.nquery.radius() == 1 {
+            println!("Semi-ladder index is at most {}", self.semi_ladder_upper);
+        }
+
+        let start = self.semi_ladder_lower+1;
+        let end = self.semi_ladder_upper;
+        'outer: for k in start..=end {
+            self.nquery.ensure_size(k);
+            for v in self.graph.vertices() {
+                let N = self.nquery.left_ball(v);
+
+                for S in N.into_iter().combinations(k) {
+                    if self.nquery.contains_semi_ladder(&S) {
+                        self.semi_ladder_lower = k;
+                        println!("Semi-ladder index is at least {}: {:?}", self.semi_ladder_lower, S);
+                        if self.semi_ladder_lower == self.semi_ladder_upper {
+                            break 'outer;
+                        }                        
+                        continue 'outer;
+                    }
+                }
+            }
+ 
+            // Semi-ladders are closed under taking sub-ladders and are symmetric, so orienting
+            // the k(k-1)/2 edges of a semi-ladder of length k along the ordering yields a vertex whose
+            // left neighbourhood contains one side of a semi-ladder of length at least (k-1)/4.
+            if self.nquery.radius() == 1 {
+                self.semi_ladder_upper = std::cmp::min(4*self.semi_ladder_lower + 1, self.semi_ladder_upper);
+            }
             break;
         }
 
-        println!("Ladder index is at most {}", self.ladder_upper);
+        if self.nquery.radius() == 1 {
+            println!("Semi-ladder index is at most {}", self.semi_ladder_upper);
+        } else {
+            println!("No upper bound on the semi-ladder index for radius {}", self.nquery.radius());
+        }
     }
 }
 
@@ -334,6 +869,7 @@ pub struct CrownAlgorithm<'a> {
     nquery: NQuery<'a>,
     crown_lower:usize,
     crown_upper:usize,
+    candidates:Option<VertexSet>,
     d: usize,
 }
 
@@ -345,19 +881,54 @@ impl<'a> CrownAlgorithm<'a> {
         let crown_lower = if m == n*(n-1)/2 { 0 } else { 1 };
         let crown_upper = d+1;
         let mut nquery = NQuery::new(graph);
-        Self{ graph, d, nquery, crown_lower, crown_upper}
+        Self{ graph, d, nquery, crown_lower, crown_upper, candidates: None}
+    }
+
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.crown_lower, self.crown_upper)
+    }
+
+    /// Use closed neighbourhoods, see `LadderAlgorithm::set_closed`.
+    pub fn set_closed(&mut self, closed:bool) {
+        self.nquery.set_closed(closed);
+        self.d = self.nquery.max_left_size();
+        self.crown_upper = self.d+1;
+    }
+
+    /// Only search for crowns whose elements are contained in `candidates`, see `LadderAlgorithm::set_candidates`.
+    pub fn set_candidates(&mut self, candidates:&VertexSet) {
+        let candidates:VertexSet = candidates.iter().filter(|x| self.graph.contains(x)).cloned().collect();
+        // A crown of size one is a candidate with a non-neighbour
+        let n = self.graph.num_vertices();
+        self.crown_lower = usize::from(candidates.iter().any(|v| (self.graph.degree(v) as usize) + 1 < n));
+        self.candidates = Some(candidates);
+    }
+
+    /// See `LadderAlgorithm::set_radius`.
+    pub fn set_radius(&mut self, radius:usize) {
+        self.nquery.set_radius(radius);
+        if radius > 1 {
+            self.crown_upper = self.nquery.max_left_ball();
+        }
     }
 
     pub fn run(&mut self) {
-        println!("Crown size is at most {}", self.crown_upper);
+        if self.nquery.radius() == 1 {
+            println!("Crown size is at most {}", self.crown_upper);
+        }
 
         let start = self.crown_lower+1;
         let end = self.crown_upper;
         'outer: for k in start..=end {
-            self.nquery.ensure_size(k);
+            match &self.candidates {
+                Some(candidates) => self.nquery.ensure_size_restricted(k, candidates),
+                None => self.nquery.ensure_size(k)
+            }
             for v in self.graph.vertices() {
-                let mut N = self.graph.left_neighbours(v);
-                N.push(*v);
+                let mut N = self.nquery.left_ball(v);
+                if let Some(candidates) = &self.candidates {
+                    N.retain(|x| candidates.contains(x));
+                }
 
                 for S in N.into_iter().combinations(k) {
                     if self.nquery.contains_crown(&S) {
@@ -371,21 +942,36 @@ impl<'a> CrownAlgorithm<'a> {
                 }
             }
  
-            self.crown_upper = std::cmp::min(self.crown_lower + 1, self.crown_upper);
+            if self.nquery.radius() == 1 && self.candidates.is_none() {
+                self.crown_upper = std::cmp::min(self.crown_lower + 1, self.crown_upper);
+            }
             break;
         }
 
-        println!("Crown size is at most {}", self.crown_upper);
+        if self.nquery.radius() == 1 {
+            println!("Crown size is at most {}", self.crown_upper);
+        } else {
+            println!("No upper bound on the crown size for radius {}", self.nquery.radius());
+        }
     }
 }
 
 
 
+/// Size and sides of the largest biclique found so far by `BicliqueAlgorithm::run_weighted`
+type WeightedBest = (usize, Option<(Vec<Vertex>, Vec<Vertex>)>);
+
+/// Result of `BicliqueAlgorithm::run_unbalanced` for one s: s, lower and upper bound on t and the two sides
+pub type UnbalancedBiclique = (usize, usize, usize, Vec<Vertex>, Vec<Vertex>);
+
 pub struct BicliqueAlgorithm<'a> {
     graph: &'a DegenGraph,
     nquery: NQuery<'a>,
     biclique_lower:usize,
     biclique_upper:usize,
+    witness:Option<(Vec<Vertex>, Vec<Vertex>)>,
+    induced: bool,
+    candidates:Option<VertexSet>,
     d: usize,
 }
 
@@ -393,28 +979,144 @@ impl<'a> BicliqueAlgorithm<'a> {
     pub fn new(graph: &'a DegenGraph) -> Self {
         let d = *graph.left_degrees().values().max().unwrap() as usize;
 
-        let m = graph.num_edges();
-        let biclique_lower = if m == 0 { 0 } else { 1 };
+        // Any edge is a biclique of size one
+        let witness = graph.vertices().find_map(|u| graph.neighbours(u).next().map(|v| (vec![*u], vec![*v])));
+        let biclique_lower = usize::from(witness.is_some());
         let biclique_upper = d;
         let mut nquery = NQuery::new(graph);
-        Self{ graph, d, nquery, biclique_lower, biclique_upper}
+        Self{ graph, d, nquery, biclique_lower, biclique_upper, witness, induced: false, candidates: None }
+    }
+
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.biclique_lower, self.biclique_upper)
+    }
+
+    /// Both sides of the largest biclique found by `run`
+    pub fn witness(&self) -> Option<&(Vec<Vertex>, Vec<Vertex>)> {
+        self.witness.as_ref()
+    }
+
+    /// Use closed neighbourhoods, see `LadderAlgorithm::set_closed`. The two sides of a biclique
+    /// may then share vertices.
+    pub fn set_closed(&mut self, closed:bool) {
+        self.nquery.set_closed(closed);
+        self.d = self.nquery.max_left_size();
+        self.biclique_upper = self.d;
+    }
+
+    /// See `LadderAlgorithm::set_radius`.
+    pub fn set_radius(&mut self, radius:usize) {
+        self.nquery.set_radius(radius);
+        if radius > 1 {
+            self.biclique_upper = self.nquery.max_left_ball();
+        }
+    }
+
+    /// Only search for induced bicliques, i.e. both sides have to be independent sets.
+    ///
+    /// An induced K_{t,t} contains K_{t,t} as a subgraph, so t <= d remains an upper bound. Moreover,
+    /// the side not containing the rightmost vertex y of an induced K_{t,t} is an independent t-subset 
+    /// of the left neighbourhood of y. The search therefore sees every induced biclique and the
+    /// bound becomes tight once it fails.
+    pub fn set_induced(&mut self, induced:bool) {
+        self.induced = induced;
+    }
+
+    /// Only search for bicliques whose first side is contained in `candidates`, the second side can 
+    /// contain any vertex. If the rightmost vertex of such a biclique lies in the second side, the first 
+    /// side is found in its left neighbourhood as before. Otherwise the rightmost vertex is a candidate
+    /// and the second side lies in its left neighbourhood, which `run` searches in a second pass. The
+    /// search therefore remains exhaustive.
+    pub fn set_candidates(&mut self, candidates:&VertexSet) {
+        let candidates:VertexSet = candidates.iter().filter(|x| self.graph.contains(x)).cloned().collect();
+        self.witness = candidates.iter().sorted_unstable()
+            .find_map(|u| self.graph.neighbours(u).next().map(|v| (vec![*u], vec![*v])));
+        self.biclique_lower = usize::from(self.witness.is_some());
+        self.candidates = Some(candidates);
+    }
+
+    /// Returns a set T of size `k` such that S x T is a (induced) biclique, if it exists.
+    fn find_partner(&self, S:&[Vertex], k:usize) -> Option<Vec<Vertex>> {
+        let mut common = self.nquery.common_neighbours(S);
+        if !self.induced {
+            common.truncate(k);
+            return Some(common);
+        }
+
+        let mut T = Vec::default();
+        if independent_subset(self.graph, &common, k, &mut T) {
+            Some(T)
+        } else {
+            None
+        }
+    }
+
+    /// Second pass of the search with candidates: returns sides S ⊆ candidates and T of size `k` 
+    /// of a (induced) biclique such that T lies in the left neighbourhood of a candidate.
+    fn find_from_candidates(&self, candidates:&VertexSet, k:usize) -> Option<(Vec<Vertex>, Vec<Vertex>)> {
+        for v in self.graph.vertices().filter(|v| candidates.contains(v)) {
+            for T in self.nquery.left_ball(v).into_iter().combinations(k) {
+                if self.induced && !is_independent(self.graph, &T) {
+                    continue;
+                }
+
+                let mut common = self.nquery.common_neighbours(&T);
+                common.retain(|u| candidates.contains(u));
+                if common.len() < k {
+                    continue;
+                }
+
+                if !self.induced {
+                    common.truncate(k);
+                    return Some((common, T));
+                }
+
+                let mut S = Vec::default();
+                if independent_subset(self.graph, &common, k, &mut S) {
+                    return Some((S, T));
+                }
+            }
+        }
+        None
     }
 
     pub fn run(&mut self) {
-        println!("Biclique size is at most {}", self.biclique_upper);
+        let name = if self.induced { "Induced biclique" } else { "Biclique" };
+        if self.induced && self.nquery.radius() > 1 {
+            println!("Induced bicliques are only supported for radius 1");
+            return;
+        }
+
+        if self.nquery.radius() == 1 {
+            println!("{name} size is at most {}", self.biclique_upper);
+        }
 
         let start = self.biclique_lower+1;
         let end = self.biclique_upper;
         'outer: for k in start..=end {
-            self.nquery.ensure_size(k);
+            match &self.candidates {
+                Some(candidates) => self.nquery.ensure_size_restricted(k, candidates),
+                None => self.nquery.ensure_size(k)
+            }
             for v in self.graph.vertices() {
-                let mut N = self.graph.left_neighbours(v);
-                N.push(*v);
+                let mut N = self.nquery.left_ball(v);
+                if let Some(candidates) = &self.candidates {
+                    N.retain(|x| candidates.contains(x));
+                }
 
                 for S in N.into_iter().combinations(k) {
-                    if self.nquery.contains_biclique(&S) {
+                    if self.induced && !is_independent(self.graph, &S) {
+                        continue;
+                    }
+
+                    if !self.nquery.contains_biclique(&S) {
+                        continue;
+                    }
+
+                    if let Some(T) = self.find_partner(&S, k) {
                         self.biclique_lower = k;
-                        println!("Biclique size is at least {}: {:?}", self.biclique_lower, S);                        
+                        println!("{name} size is at least {}: {:?} x {:?}", self.biclique_lower, S, T);
+                        self.witness = Some((S, T));
                         if self.biclique_lower == self.biclique_upper {
                             break 'outer;
                         }
@@ -422,11 +1124,1054 @@ impl<'a> BicliqueAlgorithm<'a> {
                     }
                 }
             }
+
+            if let Some(candidates) = &self.candidates {
+                if let Some((S, T)) = self.find_from_candidates(candidates, k) {
+                    self.biclique_lower = k;
+                    println!("{name} size is at least {}: {:?} x {:?}", self.biclique_lower, S, T);
+                    self.witness = Some((S, T));
+                    if self.biclique_lower == self.biclique_upper {
+                        break 'outer;
+                    }
+                    continue 'outer;
+                }
+            }
  
-            self.biclique_upper = self.biclique_lower;
+            if self.nquery.radius() == 1 {
+                self.biclique_upper = self.biclique_lower;
+            }
             break;
         }
 
-        println!("Biclique size is at most {}", self.biclique_upper);
+        if self.nquery.radius() == 1 {
+            println!("{name} size is at most {}", self.biclique_upper);
+        } else {
+            println!("No upper bound on the biclique size for radius {}", self.nquery.radius());
+        }
+    }
+
+    /// Computes the largest biclique of a graph in which every vertex v stands for `weights[v]` pairwise
+    /// non-adjacent vertices with the same neighbourhood, e.g. for a graph whose false twins were collapsed. 
+    /// A biclique with sides A, B then has size min(w(A), w(B)).
+    ///
+    /// For the rightmost vertex y of a maximal biclique, the side not containing y lies in the left 
+    /// neighbourhood of y. We therefore search all subsets A of left neighbourhoods together with their 
+    /// common neighbours, which is exhaustive. Returns the size of the largest biclique, the witness 
+    /// contains the two sides in terms of the weighted vertices. Returns `None` for radius larger
+    /// than one and in induced mode, which are not supported.
+    ///
+    /// In the worst case this visits all 2^d subsets of every left neighbourhood, i.e. O(n * 2^d) sets.
+    /// Branches whose weight cannot beat the best biclique found so far are pruned, but on graphs with
+    /// large degeneracy this is still much slower than `run`.
+    pub fn run_weighted(&mut self, weights:&VertexMap<usize>) -> Option<usize> {
+        if self.nquery.radius() > 1 {
+            println!("Weighted bicliques are only supported for radius 1");
+            return None;
+        }
+        if self.induced {
+            println!("Weighted bicliques are not supported in induced mode");
+            return None;
+        }
+
+        let mut best = (0, None);
+        for y in self.graph.vertices() {
+            let mut N = self.graph.left_neighbours(y);
+            N.sort_by_key(|u| std::cmp::Reverse(weights[u]));
+            self.weighted_search(&N, 0, &mut Vec::default(), None, weights, &mut best);
+        }
+
+        let (best, witness) = best;
+        self.biclique_lower = best;
+        self.biclique_upper = best;
+        self.witness = witness;
+        println!("Biclique size is {best}");
+        Some(best)
+    }
+
+    /// Extends `A` by vertices of `N` from index `start` on and updates `best`, the largest 
+    /// biclique found so far together with its sides.
+    fn weighted_search(&self, N:&[Vertex], start:usize, A:&mut Vec<Vertex>, common:Option<&Vec<Vertex>>, 
+                        weights:&VertexMap<usize>, best:&mut WeightedBest) {
+        if let Some(common) = common {
+            let weight_A:usize = A.iter().map(|u| weights[u]).sum();
+            let weight_common:usize = common.iter().map(|u| weights[u]).sum();
+            let size = std::cmp::min(weight_A, weight_common);
+            if size > best.0 {
+                println!("Biclique size is at least {size}: {A:?} x {common:?}");
+                *best = (size, Some((A.clone(), common.clone())));
+            }
+
+            // Adding vertices to A only shrinks the common neighbourhood
+            let remaining:usize = N[start..].iter().map(|u| weights[u]).sum();
+            if weight_common <= best.0 || weight_A + remaining <= best.0 {
+                return;
+            }
+        }
+
+        for i in start..N.len() {
+            let x = N[i];
+            let next:Vec<Vertex> = match common {
+                Some(common) => common.iter().filter(|u| self.graph.adjacent(u, &x)).cloned().collect(),
+                None => self.graph.neighbours(&x).cloned().sorted_unstable().collect()
+            };
+            A.push(x);
+            self.weighted_search(N, i+1, A, Some(&next), weights, best);
+            A.pop();
+        }
+    }
+
+    /// Computes for every s up to `max_s` bounds on the largest t such that K_{s,t} is a subgraph.
+    ///
+    /// Let S, T be the sides of a K_{s,t}. At most s*d vertices of T lie to the left of some vertex
+    /// in S, the others have S in their left neighbourhood and are counted in R[S]. Hence testing all 
+    /// s-sets in left neighbourhoods finds the optimum if it is larger than s*d, and otherwise 
+    /// t <= max R[S] + s*d. Returns a list of (s, lower bound, upper bound, S, T).
+    pub fn run_unbalanced(&mut self, max_s:usize) -> Vec<UnbalancedBiclique> {
+        let mut res = Vec::default();
+        if self.nquery.radius() > 1 {
+            println!("Unbalanced bicliques are only supported for radius 1");
+            return res;
+        }
+        if self.induced {
+            println!("Unbalanced bicliques are not supported in induced mode");
+            return res;
+        }
+
+        let mut upper = usize::MAX;
+        for s in 1..=max_s {
+            self.nquery.ensure_size(s);
+
+            let mut max_right = 0;
+            let mut best = 0;
+            let mut best_set = Vec::default();
+            for (S, right) in self.nquery.left_sets(s) {
+                max_right = std::cmp::max(max_right, right as usize);
+                if (right as usize) + s*self.d <= best {
+                    continue; // S cannot have more than `best` common neighbours
+                }
+
+                let common = self.nquery.common_neighbours(S).len();
+                if common > best {
+                    best = common;
+                    best_set = S.clone();
+                }
+            }
+
+            upper = std::cmp::min(upper, max_right + s*self.d);
+            if s > self.d {
+                // K_{s,t} has degeneracy min(s,t)
+                upper = std::cmp::min(upper, self.d);
+            }
+            upper = std::cmp::max(upper, best);
+
+            if best == 0 {
+                // No K_{s,1} exists, the empty set is not a witness
+                println!("Largest K_{{{s},t}} has t = 0 <= {upper}");
+                res.push((s, 0, upper, Vec::default(), Vec::default()));
+                continue;
+            }
+
+            let T = self.nquery.common_neighbours(&best_set);
+            if best == upper {
+                println!("Largest K_{{{s},t}} has t = {best}: {best_set:?} x {T:?}");
+            } else {
+                println!("Largest K_{{{s},t}} has {best} <= t <= {upper}: {best_set:?} x {T:?}");
+            }
+            res.push((s, best, upper, best_set, T));
+        }
+
+        println!("Pareto frontier of (s,t): {:?}", pareto_frontier(&res));
+
+        res
+    }
+}
+
+/// The pairs (s,t) with t > 0 among the results of `BicliqueAlgorithm::run_unbalanced` for which
+/// K_{s+1,t} was not found
+pub fn pareto_frontier(res:&[UnbalancedBiclique]) -> Vec<(usize, usize)> {
+    res.iter().enumerate()
+        .filter(|(i, (_, t, _, _, _))| *t > 0 && res.get(i+1).is_none_or(|(_, t_next, _, _, _)| t_next < t))
+        .map(|(_, (s, t, _, _, _))| (*s, *t))
+        .collect_vec()
+}
+
+pub struct ComplexityAlgorithm<'a> {
+    graph: &'a DegenGraph,
+    nquery: NQuery<'a>,
+    max_size: usize,
+    exact: bool,
+}
+
+impl<'a> ComplexityAlgorithm<'a> {
+    pub fn new(graph: &'a DegenGraph) -> Self {
+        let d = *graph.left_degrees().values().max().unwrap() as usize;
+
+        let max_size = d+1;
+        let exact = false;
+        let mut nquery = NQuery::new(graph);
+        Self{ graph, nquery, max_size, exact }
+    }
+
+    /// Compute the profile for all set sizes 1..=max_size.
+    pub fn set_max_size(&mut self, max_size:usize) {
+        self.max_size = max_size;
+    }
+
+    /// Compute the profile for the set system of balls of radius `radius`
+    pub fn set_radius(&mut self, radius:usize) {
+        self.nquery.set_radius(radius);
+    }
+
+    /// Compute the profile for the set system of closed neighbourhoods
+    pub fn set_closed(&mut self, closed:bool) {
+        self.nquery.set_closed(closed);
+    }
+
+    /// If `exact` is set, every k-subset of the graph is tested. Otherwise only 
+    /// sets contained in closed left neighbourhoods are tested, which yields a lower bound.
+    pub fn set_exact(&mut self, exact:bool) {
+        self.exact = exact;
+    }
+
+    /// Computes the neighbourhood complexity π(k), the maximum number of distinct 
+    /// traces on a set of size k, together with a set attaining it. 
+    pub fn run(&mut self) -> Vec<(usize, usize, Vec<Vertex>)> {
+        let mut profile = Vec::default();
+
+        for k in 1..=self.max_size {
+            self.nquery.ensure_size(k);
+
+            let mut best = 0;
+            let mut best_set = Vec::default();
+            let mut total = 0;
+            let mut num_sets = 0;
+
+            let mut test = |S:Vec<Vertex>| {
+                let traces = self.nquery.count_traces(&S);
+                total += traces;
+                num_sets += 1;
+                if traces > best {
+                    best = traces;
+                    best_set = S;
+                }
+            };
+
+            if self.exact {
+                for S in self.graph.vertices().cloned().combinations(k) {
+                    test(S);
+                }
+            } else {
+                for v in self.graph.vertices() {
+                    let N = self.nquery.left_ball(v);
+    
+                    for S in N.into_iter().combinations(k) {
+                        test(S);
+                    }
+                }
+            }
+
+            if num_sets == 0 {
+                println!("No sets of size {k} to test");
+                break;
+            }
+
+            let mean = total as f64 / num_sets as f64;
+            println!("Neighbourhood complexity pi({k}) is {} {best} (mean {mean:.2} over {num_sets} sets): {best_set:?}", 
+                if self.exact { "exactly" } else { "at least" });
+            profile.push((k, best, best_set));
+        }
+
+        println!("k\tpi(k)\tset");
+        for (k, best, best_set) in profile.iter() {
+            println!("{k}\t{best}\t{best_set:?}");
+        }
+
+        profile
+    }
+}
+
+
+
+/// Tests whether `S` is an independent set.
+fn is_independent(graph:&DegenGraph, S:&[Vertex]) -> bool {
+    S.iter().tuple_combinations().all(|(x,y)| !graph.adjacent(x, y))
+}
+
+/// Searches for an independent set of size `k` among `options` which extends `chosen`.
+fn independent_subset(graph:&DegenGraph, options:&[Vertex], k:usize, chosen:&mut Vec<Vertex>) -> bool {
+    if chosen.len() == k {
+        return true;
+    }
+
+    for (i, x) in options.iter().enumerate() {
+        if options.len() - i < k - chosen.len() {
+            break;
+        }
+        if chosen.iter().all(|y| !graph.adjacent(x, y)) {
+            chosen.push(*x);
+            if independent_subset(graph, &options[i+1..], k, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+    }
+    false
+}
+
+/// Picks one vertex from each list in `options` such that the picked vertices are pairwise distinct
+/// and non-adjacent. The choice is appended to `chosen`.
+fn independent_representatives(graph:&DegenGraph, options:&[Vec<Vertex>], chosen:&mut Vec<Vertex>) -> bool {
+    if chosen.len() == options.len() {
+        return true;
+    }
+
+    for x in options[chosen.len()].iter() {
+        if chosen.iter().all(|y| y != x && !graph.adjacent(x, y)) {
+            chosen.push(*x);
+            if independent_representatives(graph, options, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+    }
+    false
+}
+
+/// Bounds the induced matching number. The lower bound comes from a greedy matching which is
+/// then improved by searching for matchings whose left endpoints all lie in one left ball, so
+/// matchings spread over several balls are missed. The upper bound is the size of a maximal
+/// matching and is not tightened by the search.
+pub struct InducedMatchingAlgorithm<'a> {
+    graph: &'a DegenGraph,
+    nquery: NQuery<'a>,
+    matching_lower:usize,
+    matching_upper:usize,
+    witness:Vec<(Vertex, Vertex)>,
+    d: usize,
+}
+
+impl<'a> InducedMatchingAlgorithm<'a> {
+    pub fn new(graph: &'a DegenGraph) -> Self {
+        let d = *graph.left_degrees().values().max().unwrap() as usize;
+
+        // An induced matching is a matching, and a maximum matching is at most
+        // twice as large as a maximal one.
+        let mut matched = VertexSet::default();
+        for u in graph.vertices() {
+            if matched.contains(u) {
+                continue;
+            }
+            if let Some(w) = graph.neighbours(u).find(|w| !matched.contains(w)) {
+                matched.insert(*u);
+                matched.insert(*w);
+            }
+        }
+        let matching_upper = std::cmp::min(matched.len(), graph.num_vertices() / 2);
+
+        let matching_lower = 0;
+        let witness = Vec::default();
+        let mut nquery = NQuery::new(graph);
+        Self{ graph, d, nquery, matching_lower, matching_upper, witness }
+    }
+
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.matching_lower, self.matching_upper)
+    }
+
+    /// Greedily picks edges whose endpoints are not adjacent to previously picked edges.
+    fn greedy(&self) -> Vec<(Vertex, Vertex)> {
+        let mut blocked = VertexSet::default();
+        let mut res = Vec::default();
+        for u in self.graph.vertices() {
+            if blocked.contains(u) {
+                continue;
+            }
+
+            let partner = self.graph.neighbours(u)
+                .filter(|w| !blocked.contains(w))
+                .min_by_key(|w| self.graph.degree(w));
+            if let Some(w) = partner {
+                res.push((*u, *w));
+                blocked.insert(*u);
+                blocked.insert(*w);
+                blocked.extend(self.graph.neighbours(u));
+                blocked.extend(self.graph.neighbours(w));
+            }
+        }
+        res
+    }
+
+    /// Returns a partner for every vertex of `S` such that S together with the partners 
+    /// induces a perfect matching, provided that such partners exist.
+    fn partners(&self, S:&[Vertex]) -> Option<Vec<Vertex>> {
+        for (i, u) in S.iter().enumerate() {
+            if S[i+1..].iter().any(|v| self.graph.adjacent(u, v)) {
+                return None;
+            }
+        }
+
+        let options = S.iter().map(|s| {
+            self.graph.neighbours(s)
+                .filter(|b| !S.contains(b) && S.iter().all(|t| t == s || !self.graph.adjacent(b, t)))
+                .cloned().collect_vec()
+        }).collect_vec();
+
+        let mut chosen = Vec::default();
+        if independent_representatives(self.graph, &options, &mut chosen) {
+            Some(chosen)
+        } else {
+            None
+        }
+    }
+
+    pub fn run(&mut self) {
+        println!("Induced matching number is at most {}", self.matching_upper);
+
+        self.witness = self.greedy();
+        self.matching_lower = self.witness.len();
+        println!("Induced matching number is at least {} (greedy): {:?}", self.matching_lower, self.witness);
+
+        let start = self.matching_lower+1;
+        let end = std::cmp::min(self.matching_upper, self.d+1);
+        'outer: for k in start..=end {
+            self.nquery.ensure_size(k);
+            for v in self.graph.vertices() {
+                let N = self.nquery.left_ball(v);
+
+                for S in N.into_iter().combinations(k) {
+                    if !self.nquery.contains_induced_matching(&S) {
+                        continue;
+                    }
+
+                    // The traces only certify a semi-induced matching, the partners need
+                    // to be checked against the graph.
+                    if let Some(partners) = self.partners(&S) {
+                        self.matching_lower = k;
+                        self.witness = S.into_iter().zip(partners).collect();
+                        println!("Induced matching number is at least {}: {:?}", self.matching_lower, self.witness);
+                        continue 'outer;
+                    }
+                }
+            }
+            break;
+        }
+
+        println!("Induced matching number is at most {} (search only covers matchings inside one left ball)", self.matching_upper);
+    }
+}
+
+
+/// Two-sided 95% quantiles of Student's t-distribution for 1 to 30 degrees of freedom
+const T_QUANTILES:[f64; 30] = [12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228,
+                               2.201, 2.179, 2.160, 2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086,
+                               2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052, 2.048, 2.045, 2.042];
+
+/// Least-squares fit of y = c*x + b. Returns c, b and the half-width of the 95% confidence 
+/// interval of c, which is infinite if there are fewer than three points.
+fn fit_line(points:&[(f64, f64)]) -> (f64, f64, f64) {
+    let m = points.len() as f64;
+    let mean_x = points.iter().map(|(x,_)| x).sum::<f64>() / m;
+    let mean_y = points.iter().map(|(_,y)| y).sum::<f64>() / m;
+    let sxx:f64 = points.iter().map(|(x,_)| (x-mean_x)*(x-mean_x)).sum();
+    let sxy:f64 = points.iter().map(|(x,y)| (x-mean_x)*(y-mean_y)).sum();
+
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope*mean_x;
+    if points.len() < 3 {
+        return (slope, intercept, f64::INFINITY);
+    }
+
+    let df = points.len() - 2;
+    let ssr:f64 = points.iter().map(|(x,y)| (y - slope*x - intercept).powi(2)).sum();
+    let stderr = (ssr / df as f64 / sxx).sqrt();
+    let t = if df <= T_QUANTILES.len() { T_QUANTILES[df-1] } else { 1.96 };
+    (slope, intercept, t*stderr)
+}
+
+pub struct VCDensityAlgorithm<'a> {
+    graph: &'a DegenGraph,
+    nquery: NQuery<'a>,
+    max_size: usize,
+    samples: usize,
+}
+
+impl<'a> VCDensityAlgorithm<'a> {
+    pub fn new(graph: &'a DegenGraph) -> Self {
+        let d = *graph.left_degrees().values().max().unwrap() as usize;
+
+        let max_size = std::cmp::min(d+1, 10);
+        let samples = 1000;
+        let mut nquery = NQuery::new(graph);
+        Self{ graph, nquery, max_size, samples }
+    }
+
+    pub fn set_max_size(&mut self, max_size:usize) {
+        self.max_size = max_size;
+    }
+
+    /// Number of sets sampled for every size, at least one set is always sampled
+    pub fn set_samples(&mut self, samples:usize) {
+        self.samples = std::cmp::max(samples, 1);
+    }
+
+    pub fn set_radius(&mut self, radius:usize) {
+        self.nquery.set_radius(radius);
+    }
+
+    /// Estimate the VC density of the set system of closed neighbourhoods
+    pub fn set_closed(&mut self, closed:bool) {
+        self.nquery.set_closed(closed);
+    }
+
+    /// Estimates the VC density, the exponent c such that the number of traces on sets of size k 
+    /// grows like k^c. For every k we sample sets S of size k inside closed neighbourhoods (balls), 
+    /// record the largest and the mean number of traces and fit a line to log(max traces) against log(k).
+    /// Returns the fitted exponent and the half-width of its 95% confidence interval.
+    pub fn run(&mut self) -> (f64, f64) {
+        let mut rng = rand::thread_rng();
+        let ball_sizes:VertexMap<usize> = self.graph.vertices().map(|v| (*v, self.nquery.ball(v).len())).collect();
+        let mut points = Vec::default();
+
+        println!("k\tmax\tmean\tsets");
+        for k in 2..=self.max_size {
+            let centres = ball_sizes.iter().filter(|(_, size)| **size >= k).map(|(v,_)| *v).collect_vec();
+            if centres.is_empty() {
+                break;
+            }
+            self.nquery.ensure_size(k);
+
+            let mut best = 0;
+            let mut total = 0;
+            for _ in 0..self.samples {
+                let v = centres.choose(&mut rng).unwrap();
+                let ball = self.nquery.ball(v).into_iter().collect_vec();
+                let S = ball.choose_multiple(&mut rng, k).cloned().collect_vec();
+
+                let traces = self.nquery.count_traces(&S);
+                best = std::cmp::max(best, traces);
+                total += traces;
+            }
+
+            // The logarithm of zero would make the fit meaningless
+            if best == 0 {
+                println!("{k}\t0\t0\t{} (skipped)", self.samples);
+                continue;
+            }
+
+            let mean = total as f64 / self.samples as f64;
+            println!("{k}\t{best}\t{mean:.2}\t{}", self.samples);
+            points.push(((k as f64).ln(), (best as f64).ln()));
+        }
+
+        if points.len() < 2 {
+            println!("Not enough data points to estimate the VC density");
+            return (f64::NAN, f64::INFINITY);
+        }
+
+        let (density, intercept, error) = fit_line(&points);
+        println!("Fitted traces ~ {:.3} * k^{density:.3}", intercept.exp());
+        println!("VC density estimate: {density:.3} (95% confidence interval [{:.3}, {:.3}])", density - error, density + error);
+
+        (density, error)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphbench::{editgraph::EditGraph, graph::MutableGraph};
+    use crate::twins::TwinClasses;
+    use crate::testutil::powerset_graph;
+
+    fn biclique(s:Vertex, t:Vertex) -> EditGraph {
+        let mut G = EditGraph::new();
+        for u in 0..s {
+            for v in s..s+t {
+                G.add_edge(&u, &v);
+            }
+        }
+        G
+    }
+
+    #[test]
+    fn unbalanced_bicliques() {
+        let G = biclique(2, 3);
+        let D = DegenGraph::from_graph(&G);
+        let d = *D.left_degrees().values().max().unwrap() as usize;
+
+        let mut alg = BicliqueAlgorithm::new(&D);
+        let res = alg.run_unbalanced(4);
+
+        // Largest t such that K_{s,t} is a subgraph of K_{2,3}
+        let optimum = [3, 3, 2, 0];
+        for (s, t, upper, S, T) in res {
+            assert!(t <= optimum[s-1] && optimum[s-1] <= upper);
+            if s <= d {
+                assert_eq!(t, optimum[s-1]);
+            }
+            // Every right count is at most the maximum degree 3
+            assert!(upper <= 3 + s*d);
+            assert_eq!(S.len(), if t > 0 { s } else { 0 });
+            assert_eq!(T.len(), t);
+        }
+    }
+
+    #[test]
+    fn unbalanced_frontier() {
+        // K_{3,3} on {0,1,2} x {3,4,5} where 0 and 1 have four more common neighbours
+        let mut G = biclique(3, 3);
+        for v in 6..10 {
+            G.add_edge(&0, &v);
+            G.add_edge(&1, &v);
+        }
+        let D = DegenGraph::from_graph(&G);
+
+        let mut alg = BicliqueAlgorithm::new(&D);
+        let res = alg.run_unbalanced(3);
+        let t_values = res.iter().map(|(_, t, _, _, _)| *t).collect_vec();
+        assert_eq!(t_values, vec![7, 7, 3]);
+
+        assert_eq!(pareto_frontier(&res), vec![(2, 7), (3, 3)]);
+
+        // Either side of the K_{3,3} may be reported first
+        let (_, _, _, S, T) = &res[2];
+        let mut sides = vec![S.clone(), T.clone()];
+        sides.sort();
+        assert_eq!(sides, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+    }
+
+    #[test]
+    fn vc_witness_restriction() {
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+
+        let mut alg = VCAlgorithm::new(&D);
+        alg.run();
+        assert_eq!(alg.vc_dim(), 3);
+
+        // Without the witness of the full set {0,1,2} only pairs can be shattered
+        let witnesses:VertexSet = (3..v-1).collect();
+        let mut alg = VCAlgorithm::new(&D);
+        alg.set_witness_candidates(&witnesses);
+        alg.run();
+        assert_eq!(alg.vc_dim(), 2);
+    }
+
+    #[test]
+    fn vc_sides() {
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+        let ground:VertexSet = (0..3).collect();
+
+        let mut alg = VCAlgorithm::new(&D);
+        alg.set_sides(&ground, &(3..v).collect());
+        alg.run();
+        assert_eq!(alg.vc_dim(), 3);
+
+        // Only the subsets of size at most one remain as sets
+        alg = VCAlgorithm::new(&D);
+        alg.set_sides(&ground, &(3..7).collect());
+        alg.run();
+        assert_eq!(alg.vc_dim(), 1);
+    }
+
+    #[test]
+    fn dual_vc() {
+        // The subset vertices shatter {0,1,2}, but three elements can only shatter a single subset vertex
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+        let ground:VertexSet = (0..3).collect();
+        let sets:VertexSet = (3..v).collect();
+
+        let mut alg = VCAlgorithm::new(&D);
+        alg.set_sides(&ground, &sets);
+        alg.run();
+        assert_eq!(alg.vc_dim(), 3);
+
+        let mut alg = DualVCAlgorithm::new(&D, &sets);
+        alg.set_witness_candidates(&ground);
+        alg.run();
+        assert_eq!(alg.vc_dim(), 1);
+
+        // The subset vertices realise the empty trace, so e.g. the vertices of {0,2} and {1,2} are shattered
+        let mut alg = DualVCAlgorithm::new(&D, &sets);
+        alg.run();
+        assert_eq!(alg.vc_dim(), 2);
+    }
+
+    #[test]
+    fn biclique_candidates() {
+        // K_{2,2} on {0,1} x {2,3} plus an isolated vertex 4. In this ordering the side {2,3} lies in
+        // no left neighbourhood, so restricting one side to it needs the second pass over candidates.
+        let mut G = biclique(2, 2);
+        G.add_vertex(&4);
+        let D = DegenGraph::with_ordering(&G, (0..5).collect_vec().iter());
+
+        let mut alg = BicliqueAlgorithm::new(&D);
+        alg.set_candidates(&[2, 3].into_iter().collect());
+        alg.run();
+        assert_eq!(alg.bounds().0, 2);
+        let (S, T) = alg.witness().unwrap();
+        assert_eq!((S.clone(), T.clone()), (vec![2, 3], vec![0, 1]));
+
+        let mut alg = BicliqueAlgorithm::new(&D);
+        alg.set_candidates(&[4].into_iter().collect());
+        alg.run();
+        assert_eq!(alg.bounds().0, 0);
+        assert!(alg.witness().is_none());
+    }
+
+    #[test]
+    fn biclique_edge_witness() {
+        // The largest biclique of a matching is a single edge
+        let mut G = EditGraph::new();
+        G.add_edge(&0, &1);
+        G.add_edge(&2, &3);
+        let D = DegenGraph::from_graph(&G);
+
+        let mut alg = BicliqueAlgorithm::new(&D);
+        alg.run();
+        assert_eq!(alg.bounds(), (1, 1));
+        let (S, T) = alg.witness().unwrap();
+        assert!(D.adjacent(&S[0], &T[0]));
+
+        let mut alg = BicliqueAlgorithm::new(&D);
+        alg.set_candidates(&[3].into_iter().collect());
+        alg.run();
+        assert_eq!(alg.witness(), Some(&(vec![3], vec![2])));
+    }
+
+    #[test]
+    fn induced_biclique() {
+        // C4 is an induced K_{2,2}
+        let mut G = biclique(2, 2);
+        let D = DegenGraph::with_ordering(&G, (0..4).collect_vec().iter());
+        for induced in [false, true] {
+            let mut alg = BicliqueAlgorithm::new(&D);
+            alg.set_induced(induced);
+            alg.run();
+            assert_eq!(alg.bounds(), (2, 2));
+        }
+
+        // A chord inside one side leaves K_{2,2} as a subgraph, but no induced one
+        G.add_edge(&0, &1);
+        let D = DegenGraph::with_ordering(&G, (0..4).collect_vec().iter());
+        let mut alg = BicliqueAlgorithm::new(&D);
+        alg.run();
+        assert_eq!(alg.bounds(), (2, 2));
+
+        let mut alg = BicliqueAlgorithm::new(&D);
+        alg.set_induced(true);
+        alg.run();
+        assert_eq!(alg.bounds(), (1, 1));
+    }
+
+    #[test]
+    fn semi_ladder() {
+        // K_{3,3} is 3-degenerate, its longest semi-ladder uses one vertex of each side
+        let G = biclique(3, 3);
+        let D = DegenGraph::with_ordering(&G, (0..6).collect_vec().iter());
+
+        let mut alg = SemiLadderAlgorithm::new(&D);
+        assert_eq!(alg.bounds(), (1, 4*3+1));
+        alg.run();
+        assert_eq!(alg.bounds(), (2, 4*2+1));
+
+        let mut alg = SemiLadderAlgorithm::new(&D);
+        alg.set_closed(true);
+        assert_eq!(alg.bounds(), (1, 4*4+1));
+    }
+
+    #[test]
+    fn weighted_biclique() {
+        // K_{3,4} collapses into a single edge whose endpoints stand for three and four vertices
+        let G = biclique(3, 4);
+        let D = DegenGraph::from_graph(&G);
+        let mut alg = BicliqueAlgorithm::new(&D);
+        alg.run();
+        assert_eq!(alg.bounds(), (3, 3));
+
+        let twins = TwinClasses::new(&G, false);
+        let H = twins.reduce(&G);
+        assert_eq!(H.num_vertices(), 2);
+        let D = DegenGraph::from_graph(&H);
+        let mut alg = BicliqueAlgorithm::new(&D);
+        assert_eq!(alg.run_weighted(&twins.multiplicities()), Some(3));
+        let (A, B) = alg.witness().unwrap();
+        assert_eq!(twins.lift(A).len() + twins.lift(B).len(), 7);
+
+        let mut alg = BicliqueAlgorithm::new(&D);
+        alg.set_radius(2);
+        assert_eq!(alg.run_weighted(&twins.multiplicities()), None);
+    }
+
+    #[test]
+    fn ladder_candidates() {
+        // Half-graph with a_i = 3+i and b_j = j where a_i ~ b_j iff j <= i, plus an isolated vertex 6
+        let mut G = EditGraph::new();
+        for i in 0..3 {
+            for j in 0..=i {
+                G.add_edge(&(3+i), &j);
+            }
+        }
+        G.add_vertex(&6);
+        let D = DegenGraph::with_ordering(&G, (0..7).collect_vec().iter());
+
+        for (candidates, ladder) in [(vec![0, 1, 2], 3), (vec![0, 2], 2), (vec![6], 0)] {
+            let mut alg = LadderAlgorithm::new(&D);
+            alg.set_candidates(&candidates.into_iter().collect());
+            alg.run();
+            assert_eq!(alg.bounds().0, ladder);
+        }
+    }
+
+    #[test]
+    fn crown_candidates() {
+        // K_{3,3} minus a perfect matching on b_j = j and a_i = 3+i, plus a vertex 6 adjacent to all b_j
+        // which puts them into one left neighbourhood
+        let mut G = EditGraph::new();
+        for i in 0..3 {
+            for j in 0..3 {
+                if i != j {
+                    G.add_edge(&(3+i), &j);
+                }
+            }
+            G.add_edge(&6, &i);
+        }
+        let D = DegenGraph::with_ordering(&G, (0..7).collect_vec().iter());
+
+        for (candidates, crown) in [(vec![0, 1, 2], 3), (vec![0, 1], 2), (vec![0], 1)] {
+            let mut alg = CrownAlgorithm::new(&D);
+            alg.set_candidates(&candidates.into_iter().collect());
+            alg.run();
+            assert_eq!(alg.bounds(), (crown, 4));
+        }
+    }
+
+    #[test]
+    fn induced_matching() {
+        // Three disjoint edges form an induced matching
+        let mut G = EditGraph::new();
+        for u in [0, 2, 4] {
+            G.add_edge(&u, &(u+1));
+        }
+        let D = DegenGraph::with_ordering(&G, (0..6).collect_vec().iter());
+        let mut alg = InducedMatchingAlgorithm::new(&D);
+        alg.run();
+        assert_eq!(alg.bounds(), (3, 3));
+
+        // On the path 0-1-2-3-4-5 the perfect matching is not induced, at most two edges are
+        let mut G = EditGraph::new();
+        for u in 0..5 {
+            G.add_edge(&u, &(u+1));
+        }
+        let D = DegenGraph::with_ordering(&G, (0..6).collect_vec().iter());
+        let mut alg = InducedMatchingAlgorithm::new(&D);
+        assert_eq!(alg.greedy(), vec![(0, 1), (3, 4)]);
+        assert_eq!(alg.partners(&[0, 3]), Some(vec![1, 4]));
+        assert_eq!(alg.partners(&[0, 2]), None);
+        alg.run();
+        assert_eq!(alg.bounds(), (2, 3));
+        for ((a, b), (x, y)) in alg.witness.iter().tuple_combinations() {
+            assert!(!D.adjacent(a, x) && !D.adjacent(a, y) && !D.adjacent(b, x) && !D.adjacent(b, y));
+        }
+    }
+
+    #[test]
+    fn ladder_sides() {
+        // Half-graph with a_i = 3+i and b_j = j where a_i ~ b_j iff j <= i, the neighbourhoods 
+        // of the a_i form a chain on the b_j
+        let mut G = EditGraph::new();
+        for i in 0..3 {
+            for j in 0..=i {
+                G.add_edge(&(3+i), &j);
+            }
+        }
+        let D = DegenGraph::with_ordering(&G, (0..6).collect_vec().iter());
+        let ground:VertexSet = (0..3).collect();
+        let sets:VertexSet = (3..6).collect();
+
+        let mut alg = LadderAlgorithm::new(&D);
+        alg.set_sides(&ground, &sets);
+        assert_eq!(alg.bounds().0, 1);
+        alg.run();
+        assert_eq!(alg.bounds().0, 3);
+
+        // Without witnesses there is no ladder
+        let mut alg = LadderAlgorithm::new(&D);
+        alg.set_sides(&ground, &VertexSet::default());
+        assert_eq!(alg.bounds().0, 0);
+        alg.run();
+        assert_eq!(alg.bounds().0, 0);
+    }
+
+    #[test]
+    fn complexity() {
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+
+        let mut alg = ComplexityAlgorithm::new(&D);
+        alg.set_max_size(3);
+        alg.set_exact(true);
+        let profile = alg.run();
+        let values = profile.iter().map(|(k, pi, _)| (*k, *pi)).collect_vec();
+        assert_eq!(values, vec![(1, 2), (2, 4), (3, 8)]);
+        assert_eq!(profile[2].2, vec![0, 1, 2]);
+
+        // The set {0,1,2} lies in the left neighbourhood of the last subset vertex
+        let mut alg = ComplexityAlgorithm::new(&D);
+        alg.set_max_size(3);
+        let profile = alg.run();
+        assert_eq!(profile[2].1, 8);
+    }
+
+    #[test]
+    fn witness_enumeration() {
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+
+        // {0,1,2} is the only shattered set of size three
+        let mut alg = VCAlgorithm::new(&D);
+        alg.run();
+        assert_eq!(alg.enumerate_witnesses(None), 1);
+        assert_eq!(alg.enumerate_witnesses(Some(1)), 1);
+    }
+
+    #[test]
+    fn shatter_profile() {
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+
+        // Singletons: all vertices but the one without neighbours. Pairs: the pairs of {0,1,2}
+        // and the three pairs of subset vertices whose sets overlap without containment.
+        let mut alg = VCAlgorithm::new(&D);
+        let profile = alg.shatter_profile(3, 0);
+        assert_eq!(profile, vec![1.0, 10.0, 6.0, 1.0]);
+
+        // The walks below {0,1,2} have no children, so there is nothing to estimate
+        let mut alg = VCAlgorithm::new(&D);
+        let profile = alg.shatter_profile(3, 10);
+        assert_eq!(profile, vec![1.0, 10.0, 6.0, 1.0]);
+    }
+
+    #[test]
+    fn local_vc() {
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+
+        // Left neighbourhoods: {0} for vertex 0, nothing for the empty set, {0,1,2} for the full set
+        let mut alg = VCAlgorithm::new(&D);
+        let local = alg.local_vc_dimensions(false);
+        assert_eq!(local[&0], 1);
+        assert_eq!(local[&3], 0);
+        assert_eq!(local[&(v-1)], 3);
+
+        // The neighbourhood of 0 contains the vertices of {0,1} and {0,2}, which are shattered
+        let local = alg.local_vc_dimensions(true);
+        assert_eq!(local[&0], 2);
+        assert_eq!(local[&(v-1)], 3);
+    }
+
+    #[test]
+    fn line_fit() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.0)];
+        let (slope, intercept, error) = fit_line(&points);
+        assert!((slope - 2.0).abs() < 1e-9);
+        assert!((intercept - 1.0).abs() < 1e-9);
+        assert!(error < 1e-9);
+
+        let (_, _, error) = fit_line(&points[..2]);
+        assert!(error.is_infinite());
+    }
+
+    #[test]
+    fn vc_density() {
+        // In K_6 every set S of size k < 6 has the k+1 traces S and S - x for x in S
+        let mut G = EditGraph::new();
+        for u in 0..6 {
+            for v in u+1..6 {
+                G.add_edge(&u, &v);
+            }
+        }
+        let D = DegenGraph::from_graph(&G);
+
+        let mut alg = VCDensityAlgorithm::new(&D);
+        alg.set_max_size(4);
+        alg.set_samples(5);
+        let (density, error) = alg.run();
+
+        let points = (2..=4).map(|k:usize| ((k as f64).ln(), ((k+1) as f64).ln())).collect_vec();
+        let (expected, _, expected_error) = fit_line(&points);
+        assert!((density - expected).abs() < 1e-9);
+        assert!((error - expected_error).abs() < 1e-9);
+        // Zero samples are raised to one, which suffices since all sets of a size have the same traces
+        let mut alg = VCDensityAlgorithm::new(&D);
+        alg.set_max_size(4);
+        alg.set_samples(0);
+        let (density, _) = alg.run();
+        assert!((density - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn degree_profile_bound() {
+        // Each element of a shattered triple lies in one set of size 3, two of size 2 and one of size 1
+        assert_eq!(generate_degree_profile(3), vec![3, 2, 2, 1]);
+        assert!(dominates_profile(&vec![4, 2, 2, 1, 1], &vec![3, 2, 2, 1]));
+        assert!(!dominates_profile(&vec![3, 2, 1, 1], &vec![3, 2, 2, 1]));
+
+        // Perfect matching: d+1 = 2, but no neighbourhood has two elements, so the profiles give 1
+        let mut G = EditGraph::new();
+        for u in 0..10 {
+            G.add_edge(&(2*u), &(2*u+1));
+        }
+        let D = DegenGraph::from_graph(&G);
+
+        let mut alg = VCAlgorithm::new(&D);
+        assert_eq!(alg.compute_upper_bound(), 1);
+        alg.run();
+        assert_eq!(alg.vc_dim(), 1);
+
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+        let mut alg = VCAlgorithm::new(&D);
+        assert!(alg.compute_upper_bound() >= 3);
+    }
+
+    #[test]
+    fn closed_witness_enumeration() {
+        // Path c-a-b-e with c=0, a=1, b=2, e=3 and an isolated vertex f=4. In the closed
+        // neighbourhoods {a,b} is shattered by the trace of a itself.
+        let mut G = EditGraph::new();
+        G.add_edge(&0, &1);
+        G.add_edge(&1, &2);
+        G.add_edge(&2, &3);
+        G.add_vertex(&4);
+        let D = DegenGraph::from_graph(&G);
+
+        let mut alg = VCAlgorithm::new(&D);
+        alg.set_closed(true);
+        alg.run();
+        assert_eq!(alg.vc_dim(), 2);
+        assert_eq!(alg.enumerate_witnesses(None), 3);
+    }
+
+    #[test]
+    fn ball_witness_enumeration() {
+        // Balls of radius two on a long path are intervals, every shattered pair lies in one ball
+        let mut G = EditGraph::new();
+        for u in 0..29 {
+            G.add_edge(&u, &(u+1));
+        }
+        let D = DegenGraph::from_graph(&G);
+
+        let mut alg = VCAlgorithm::new(&D);
+        alg.set_radius(2);
+        alg.run();
+        assert_eq!(alg.vc_dim(), 2);
+
+        let expected = (0..30).combinations(2).filter(|S| alg.nquery.is_shattered(S)).count();
+        assert!(expected > 0);
+        assert_eq!(alg.enumerate_witnesses(None), expected);
     }
 }
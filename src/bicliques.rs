@@ -0,0 +1,188 @@
+use graphbench::graph::*;
+use graphbench::degengraph::DegenGraph;
+
+use itertools::*;
+
+use crate::ordering::linear_ordering;
+
+/// Streams all maximal bicliques (A,B) of a graph, i.e. pairs of vertex sets where every vertex in A is
+/// adjacent to every vertex in B and neither side can be extended.
+///
+/// Let y be the rightmost vertex of a maximal biclique. Then the side not containing y lies in the left
+/// neighbourhood of y, and the biclique is determined by this side A via B = N(A). We therefore
+/// enumerate all subsets A of left neighbourhoods and only report (A, N(A)) if A = N(N(A)) and y is the
+/// rightmost vertex, which makes every biclique appear exactly once. Subsets are enumerated by size,
+/// starting from the smaller of the two thresholds.
+///
+/// A biclique is reported if one of its sides has at least `min_left` and the other at least
+/// `min_right` vertices, the side satisfying `min_left` comes first.
+pub struct MaximalBicliques<'a> {
+    graph: &'a DegenGraph,
+    index: VertexMap<usize>,
+    order: Vec<Vertex>,
+    pos: usize,
+    left: Vec<Vertex>,
+    subset: Option<Vec<usize>>,
+    min_left: usize,
+    min_right: usize,
+}
+
+impl<'a> MaximalBicliques<'a> {
+    pub fn new(graph: &'a DegenGraph, min_left:usize, min_right:usize) -> Self {
+        let order = linear_ordering(graph);
+        let index = order.iter().enumerate().map(|(i,v)| (*v, i)).collect();
+        MaximalBicliques{ graph, index, order, pos: 0, left: Vec::default(), subset: None, min_left, min_right }
+    }
+
+    /// Returns all vertices adjacent to every vertex in the non-empty set `S`, sorted.
+    fn common_neighbours(&self, S:&[Vertex]) -> Vec<Vertex> {
+        let pivot = S.iter().min_by_key(|u| self.graph.degree(u)).unwrap();
+        self.graph.neighbours(pivot)
+            .filter(|u| S.iter().all(|s| s == pivot || self.graph.adjacent(u, s)))
+            .cloned().sorted_unstable().collect()
+    }
+
+    /// Moves `subset` to the next set of indices into `left`, first in lexicographic order among
+    /// sets of the same size and then to the next size. Returns `false` once all sets have been seen.
+    fn advance(subset:&mut Vec<usize>, n:usize) -> bool {
+        let k = subset.len();
+        if let Some(i) = (0..k).rev().find(|&i| subset[i] < n - k + i) {
+            subset[i] += 1;
+            for j in i+1..k {
+                subset[j] = subset[j-1] + 1;
+            }
+            return true;
+        }
+
+        if k < n {
+            *subset = (0..=k).collect();
+            return true;
+        }
+        false
+    }
+
+    /// Tests whether the subset of `left` given by the indices `subset` is one side of a maximal
+    /// biclique whose rightmost vertex is `y`, and returns the biclique in that case.
+    fn test(&self, y:&Vertex, subset:&[usize]) -> Option<(Vec<Vertex>, Vec<Vertex>)> {
+        let A = subset.iter().map(|i| self.left[*i]).sorted_unstable().collect_vec();
+
+        let B = self.common_neighbours(&A);
+        if B.len() < std::cmp::min(self.min_left, self.min_right) {
+            return None;
+        }
+
+        // A needs to be closed, otherwise the biclique is found from its closure
+        if self.common_neighbours(&B) != A {
+            return None;
+        }
+
+        let y_ix = self.index[y];
+        if B.iter().any(|u| self.index[u] > y_ix) {
+            return None;
+        }
+
+        if A.len() >= self.min_left && B.len() >= self.min_right {
+            Some((A, B))
+        } else if B.len() >= self.min_left && A.len() >= self.min_right {
+            Some((B, A))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Iterator for MaximalBicliques<'a> {
+    type Item = (Vec<Vertex>, Vec<Vertex>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.order.len() {
+            let y = self.order[self.pos];
+            let mut subset = match self.subset.take() {
+                Some(subset) => subset,
+                None => {
+                    self.left = self.graph.left_neighbours(&y);
+                    let k = std::cmp::max(1, std::cmp::min(self.min_left, self.min_right));
+                    (0..k).collect()
+                }
+            };
+
+            if subset.len() <= self.left.len() {
+                loop {
+                    let biclique = self.test(&y, &subset);
+                    let more = Self::advance(&mut subset, self.left.len());
+                    if biclique.is_some() {
+                        if more {
+                            self.subset = Some(subset);
+                        } else {
+                            self.pos += 1;
+                        }
+                        return biclique;
+                    }
+                    if !more {
+                        break;
+                    }
+                }
+            }
+
+            self.pos += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphbench::{editgraph::EditGraph, graph::MutableGraph};
+
+    #[test]
+    fn path() {
+        let mut G = EditGraph::new();
+        G.add_edge(&0, &1);
+        G.add_edge(&1, &2);
+        let D = DegenGraph::from_graph(&G);
+
+        let bicliques = MaximalBicliques::new(&D, 1, 1).collect_vec();
+        assert_eq!(bicliques.len(), 1);
+        let (A, B) = &bicliques[0];
+        assert!((A == &vec![0, 2] && B == &vec![1]) || (A == &vec![1] && B == &vec![0, 2]));
+    }
+
+    #[test]
+    fn complete_bipartite() {
+        // K_{2,3} with a pendant vertex attached to one of the larger side
+        let mut G = EditGraph::new();
+        for u in 0..2 {
+            for v in 2..5 {
+                G.add_edge(&u, &v);
+            }
+        }
+        G.add_edge(&4, &5);
+        let D = DegenGraph::from_graph(&G);
+
+        let mut bicliques = MaximalBicliques::new(&D, 1, 1).collect_vec();
+        bicliques.sort();
+        assert_eq!(bicliques.len(), 2);
+
+        let bicliques = MaximalBicliques::new(&D, 2, 3).collect_vec();
+        assert_eq!(bicliques, vec![(vec![0, 1], vec![2, 3, 4])]);
+
+        let bicliques = MaximalBicliques::new(&D, 3, 2).collect_vec();
+        assert_eq!(bicliques, vec![(vec![2, 3, 4], vec![0, 1])]);
+    }
+
+    #[test]
+    fn large_left_degree() {
+        // K_{65,65} ordered side by side, so the left degrees exceed 64
+        let mut G = EditGraph::new();
+        for u in 0..65 {
+            for v in 65..130 {
+                G.add_edge(&u, &v);
+            }
+        }
+        let D = DegenGraph::with_ordering(&G, (0..130).collect_vec().iter());
+
+        let bicliques = MaximalBicliques::new(&D, 65, 65).collect_vec();
+        assert_eq!(bicliques, vec![((0..65).collect_vec(), (65..130).collect_vec())]);
+    }
+}
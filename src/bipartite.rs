@@ -0,0 +1,61 @@
+use std::collections::VecDeque;
+
+use graphbench::graph::*;
+use graphbench::degengraph::DegenGraph;
+
+/// Computes a proper 2-colouring of `graph` and returns the first colour class, or `None` if
+/// the graph is not bipartite. In every component the smallest vertex receives the first colour.
+pub fn two_colouring(graph:&DegenGraph) -> Option<VertexSet> {
+    let mut colour:VertexMap<bool> = VertexMap::default();
+    let mut vertices:Vec<Vertex> = graph.vertices().cloned().collect();
+    vertices.sort_unstable();
+
+    for root in vertices {
+        if colour.contains_key(&root) {
+            continue;
+        }
+
+        colour.insert(root, false);
+        let mut queue:VecDeque<Vertex> = VecDeque::default();
+        queue.push_back(root);
+        while let Some(u) = queue.pop_front() {
+            let c = colour[&u];
+            for x in graph.neighbours(&u) {
+                match colour.get(x) {
+                    Some(cx) if *cx == c => return None,
+                    Some(_) => {},
+                    None => {
+                        colour.insert(*x, !c);
+                        queue.push_back(*x);
+                    }
+                }
+            }
+        }
+    }
+
+    Some(colour.into_iter().filter(|(_, c)| !c).map(|(v, _)| v).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphbench::{editgraph::EditGraph, graph::MutableGraph};
+
+    #[test]
+    fn cycles() {
+        let mut G = EditGraph::new();
+        for i in 0..6 {
+            G.add_edge(&i, &((i+1) % 6));
+        }
+        G.add_edge(&10, &11);
+        let D = DegenGraph::from_graph(&G);
+
+        let side = two_colouring(&D).unwrap();
+        let expected:VertexSet = [0, 2, 4, 10].into_iter().collect();
+        assert_eq!(side, expected);
+
+        G.add_edge(&0, &2);
+        let D = DegenGraph::from_graph(&G);
+        assert!(two_colouring(&D).is_none());
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::VecDeque;
+
+use graphbench::graph::*;
+use graphbench::editgraph::EditGraph;
+
+/// Computes the connected components of `graph` by breadth-first search. Every component is
+/// sorted and the components are returned by decreasing size, ties broken by their smallest vertex.
+pub fn connected_components(graph:&EditGraph) -> Vec<Vec<Vertex>> {
+    let mut seen = VertexSet::default();
+    let mut vertices:Vec<Vertex> = graph.vertices().cloned().collect();
+    vertices.sort_unstable();
+
+    let mut components = Vec::default();
+    for root in vertices {
+        if !seen.insert(root) {
+            continue;
+        }
+
+        let mut component = vec![root];
+        let mut queue:VecDeque<Vertex> = VecDeque::default();
+        queue.push_back(root);
+        while let Some(u) = queue.pop_front() {
+            for x in graph.neighbours(&u) {
+                if seen.insert(*x) {
+                    component.push(*x);
+                    queue.push_back(*x);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+
+    // Stable sort keeps components of equal size ordered by their smallest vertex
+    components.sort_by_key(|C| std::cmp::Reverse(C.len()));
+    components
+}
+
+/// The subgraph of `graph` induced by `vertices`, vertices not contained in `graph` are ignored.
+pub fn induced_subgraph(graph:&EditGraph, vertices:&[Vertex]) -> EditGraph {
+    let members:VertexSet = vertices.iter().cloned().collect();
+    let mut res = EditGraph::new();
+    for v in vertices.iter().filter(|v| graph.contains(v)) {
+        res.add_vertex(v);
+        for u in graph.neighbours(v) {
+            if members.contains(u) {
+                res.add_edge(v, u);
+            }
+        }
+    }
+    res
+}
+
+/// How the values of a statistic on the connected components combine into its value on the whole graph
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregation {
+    Max,
+    Sum
+}
+
+/// Computes a statistic on every connected component of `graph` and combines the lower and upper bounds
+/// returned by `compute` according to `aggregation`. Returns the combined bounds and, for `Aggregation::Max`,
+/// the index (starting at one) of the component with the largest lower bound.
+///
+/// If `add_dummy` is set and there are several components, an isolated vertex is added to every component.
+/// It realises the empty trace which the vertices of the other components provide in the whole graph.
+/// For `Aggregation::Max`, components whose a-priori upper bound `bound` does not exceed the best lower bound
+/// found so far are skipped.
+pub fn aggregate_components<E>(graph:&EditGraph, aggregation:Aggregation, add_dummy:bool, 
+        bound:impl Fn(&EditGraph) -> Option<usize>,
+        mut compute:impl FnMut(&EditGraph) -> Result<Option<(usize, usize)>, E>) -> Result<(usize, usize, Option<usize>), E> {
+    let components = connected_components(graph);
+    println!("Found {} connected components", components.len());
+
+    let dummy = graph.vertices().max().map_or(0, |v| v+1);
+    let add_dummy = add_dummy && components.len() > 1;
+
+    let (mut lower, mut upper) = (0, 0);
+    let mut best_component = None;
+    for (i, C) in components.iter().enumerate() {
+        let mut H = induced_subgraph(graph, C);
+        println!("Component {} of {} with n={} and m={}", i+1, components.len(), H.num_vertices(), H.num_edges());
+        if add_dummy {
+            H.add_vertex(&dummy);
+        }
+
+        if aggregation == Aggregation::Max {
+            if let Some(bound) = bound(&H).filter(|bound| *bound <= lower) {
+                println!("Skipping component, its value is at most {bound}");
+                continue;
+            }
+        }
+
+        if let Some((comp_lower, comp_upper)) = compute(&H)? {
+            match aggregation {
+                Aggregation::Sum => {
+                    lower += comp_lower;
+                    upper += comp_upper;
+                },
+                Aggregation::Max => {
+                    if best_component.is_none() || comp_lower > lower {
+                        lower = comp_lower;
+                        best_component = Some(i+1);
+                    }
+                    upper = std::cmp::max(upper, comp_upper);
+                }
+            }
+        }
+    }
+
+    Ok((lower, upper, best_component))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphbench::degengraph::DegenGraph;
+    use crate::algorithms::{BicliqueAlgorithm, InducedMatchingAlgorithm};
+
+    #[test]
+    fn components() {
+        // Triangle, a path on two vertices and an isolated vertex
+        let mut G = EditGraph::new();
+        G.add_edge(&5, &6);
+        G.add_edge(&6, &7);
+        G.add_edge(&7, &5);
+        G.add_edge(&1, &3);
+        G.add_vertex(&2);
+
+        let C = connected_components(&G);
+        assert_eq!(C, vec![vec![5, 6, 7], vec![1, 3], vec![2]]);
+
+        let H = induced_subgraph(&G, &[5, 6, 1]);
+        assert_eq!(H.num_vertices(), 3);
+        assert_eq!(H.num_edges(), 1);
+    }
+
+    #[test]
+    fn induced() {
+        // 3x3 grid with vertex 3*i + j in row i and column j
+        let mut G = EditGraph::new();
+        for i in 0..3 {
+            for j in 0..3 {
+                if j < 2 {
+                    G.add_edge(&(3*i + j), &(3*i + j + 1));
+                }
+                if i < 2 {
+                    G.add_edge(&(3*i + j), &(3*i + j + 3));
+                }
+            }
+        }
+
+        // First row, the centre and a vertex which is not in the grid
+        let H = induced_subgraph(&G, &[0, 1, 2, 4, 100]);
+        assert_eq!(H.num_vertices(), 4);
+        assert_eq!(H.num_edges(), 3);
+        assert!(!H.contains(&100));
+
+        // Corners are pairwise non-adjacent but remain as isolated vertices
+        let H = induced_subgraph(&G, &[0, 2, 6, 8]);
+        assert_eq!(H.num_vertices(), 4);
+        assert_eq!(H.num_edges(), 0);
+        assert_eq!(connected_components(&H).len(), 4);
+    }
+
+    #[test]
+    fn aggregation() {
+        // A star with five leaves, a K_{2,2} and a single edge. The largest biclique lies in the
+        // second component, the largest induced matching is the sum over all components.
+        let mut G = EditGraph::new();
+        for v in 1..6 {
+            G.add_edge(&0, &v);
+        }
+        for (u, v) in [(6, 8), (6, 9), (7, 8), (7, 9), (10, 11)] {
+            G.add_edge(&u, &v);
+        }
+
+        let biclique = |H:&EditGraph| -> Result<Option<(usize, usize)>, ()> {
+            let D = DegenGraph::from_graph(H);
+            let mut alg = BicliqueAlgorithm::new(&D);
+            alg.run();
+            Ok(Some(alg.bounds()))
+        };
+        let res = aggregate_components(&G, Aggregation::Max, false, |_| None, biclique);
+        assert_eq!(res, Ok((2, 2, Some(2))));
+
+        // The single edge is 1-degenerate and cannot contain a larger biclique than the K_{2,2}
+        let degeneracy = |H:&EditGraph| DegenGraph::from_graph(H).left_degrees().values().max().map(|d| *d as usize);
+        let mut computed = 0;
+        let res = aggregate_components(&G, Aggregation::Max, false, degeneracy, |H| { computed += 1; biclique(H) });
+        assert_eq!(res, Ok((2, 2, Some(2))));
+        assert_eq!(computed, 2);
+
+        let matching = |H:&EditGraph| -> Result<Option<(usize, usize)>, ()> {
+            let D = DegenGraph::from_graph(H);
+            let mut alg = InducedMatchingAlgorithm::new(&D);
+            alg.run();
+            Ok(Some(alg.bounds()))
+        };
+        let (lower, _, best) = aggregate_components(&G, Aggregation::Sum, false, |_| Some(0), matching).unwrap();
+        assert_eq!((lower, best), (3, None));
+
+        // The dummy vertex is isolated, so it adds one vertex to every component
+        let res = aggregate_components(&G, Aggregation::Sum, true, |_| None, |H| Ok::<_, ()>(Some((H.num_vertices(), 0))));
+        assert_eq!(res, Ok((G.num_vertices() + 3, 0, None)));
+    }
+}
@@ -0,0 +1,207 @@
+use graphbench::graph::*;
+use graphbench::degengraph::DegenGraph;
+
+use itertools::*;
+
+use crate::setfunc::SmallSetFunc;
+
+/// Greedy lower bounds for the VC dimension, the ladder index and the biclique size which do not
+/// build the index of `NQuery`. Traces are computed directly from the adjacency lists, which costs
+/// time proportional to the sum of the degrees of the tested set, so the search is limited by
+/// the number of sets it may test.
+pub struct HeuristicAlgorithm<'a> {
+    graph: &'a DegenGraph,
+    order: Vec<Vertex>,
+    budget: usize,
+    tests: usize,
+}
+
+impl<'a> HeuristicAlgorithm<'a> {
+    pub fn new(graph: &'a DegenGraph) -> Self {
+        // Large left neighbourhoods are the most promising places to look for structures
+        let left_degrees = graph.left_degrees();
+        let order = graph.vertices().cloned().sorted_by_key(|v| std::cmp::Reverse(left_degrees[v])).collect();
+        HeuristicAlgorithm{ graph, order, budget: 100_000, tests: 0 }
+    }
+
+    /// Maximum number of sets tested by each search
+    pub fn set_budget(&mut self, budget:usize) {
+        self.budget = budget;
+    }
+
+    fn exhausted(&self) -> bool {
+        self.tests >= self.budget
+    }
+
+    /// Counts the traces N(v) ∩ S of all vertices v directly from the adjacency lists.
+    fn traces(&mut self, S:&[Vertex]) -> SmallSetFunc {
+        self.tests += 1;
+
+        let mut traces:VertexMap<Vec<Vertex>> = VertexMap::default();
+        for s in S {
+            for u in self.graph.neighbours(s) {
+                traces.entry(*u).or_default().push(*s);
+            }
+        }
+
+        let mut I = SmallSetFunc::new(S);
+        for X in traces.values() {
+            I[X] += 1;
+        }
+        I[&vec![]] = (self.graph.num_vertices() - traces.len()) as i32;
+        I
+    }
+
+    /// Candidates inside the closed left neighbourhood of `v`, high-degree vertices first.
+    fn candidates(&self, v:&Vertex) -> Vec<Vertex> {
+        let mut N = self.graph.left_neighbours(v);
+        N.push(*v);
+        N.sort_by_key(|u| std::cmp::Reverse(self.graph.degree(u)));
+        N
+    }
+
+    /// Greedily grows a set inside every closed left neighbourhood by adding each vertex
+    /// for which `property` remains true. Returns the largest set found.
+    fn grow<F>(&mut self, name:&str, property:F) -> Vec<Vertex> where F: Fn(&SmallSetFunc) -> bool {
+        self.tests = 0;
+        let mut best = Vec::default();
+        for v in self.order.clone() {
+            if self.exhausted() {
+                break;
+            }
+
+            let N = self.candidates(&v);
+            if N.len() <= best.len() {
+                continue;
+            }
+
+            let mut S = Vec::default();
+            for x in N {
+                if self.exhausted() {
+                    break;
+                }
+                S.push(x);
+                let I = self.traces(&S);
+                if !property(&I) {
+                    S.pop();
+                }
+            }
+
+            if S.len() > best.len() {
+                println!("{name} is at least {}: {:?}", S.len(), S);
+                best = S;
+            }
+        }
+
+        println!("Used {} of {} tests", self.tests, self.budget);
+        best
+    }
+
+    /// Returns a shattered set found by the greedy search.
+    pub fn run_vc(&mut self) -> Vec<Vertex> {
+        let res = self.grow("VC dimension", |I| I.count_nonzero() == 1 << I.size());
+        println!("Largest shattered set found: {} (heuristic lower bound)", res.len());
+        res
+    }
+
+    /// Returns the elements of a ladder found by the greedy search.
+    pub fn run_ladder(&mut self) -> Vec<Vertex> {
+        let res = self.grow("Ladder index", |I| I.is_ladder());
+        println!("Largest ladder found: {} (heuristic lower bound)", res.len());
+        res
+    }
+
+    /// Returns both sides of a biclique found by the greedy search. Inside every left neighbourhood
+    /// we repeatedly add the vertex which keeps the most common neighbours.
+    pub fn run_biclique(&mut self) -> (Vec<Vertex>, Vec<Vertex>) {
+        self.tests = 0;
+        let mut best = (Vec::default(), Vec::default());
+        for v in self.order.clone() {
+            if self.exhausted() {
+                break;
+            }
+
+            let mut N = self.graph.left_neighbours(&v);
+            if N.len() <= best.0.len() {
+                continue;
+            }
+
+            // Start with the vertex of largest degree, every vertex of N has v as a common neighbour
+            N.sort_by_key(|u| self.graph.degree(u));
+            let first = N.pop().unwrap();
+            let mut S:Vec<Vertex> = vec![first];
+            let mut common:Vec<Vertex> = self.graph.neighbours(&first).cloned().collect();
+            while !N.is_empty() && common.len() > best.0.len() && !self.exhausted() {
+                // Vertex of N which is adjacent to the most common neighbours of S
+                let (ix, next) = N.iter().enumerate()
+                    .map(|(i, x)| (i, common.iter().filter(|u| self.graph.adjacent(u, x)).count()))
+                    .max_by_key(|(_, count)| *count).unwrap();
+                self.tests += N.len();
+
+                let x = N.swap_remove(ix);
+                if next <= best.0.len() {
+                    break;
+                }
+                common.retain(|u| self.graph.adjacent(u, &x));
+                S.push(x);
+
+                let size = std::cmp::min(S.len(), common.len());
+                if size > best.0.len() {
+                    let T = common[..size].to_vec();
+                    println!("Biclique size is at least {size}: {:?} x {T:?}", &S[..size]);
+                    best = (S[..size].to_vec(), T);
+                }
+            }
+        }
+
+        println!("Used {} of {} tests", self.tests, self.budget);
+        println!("Largest biclique found: {} (heuristic lower bound)", best.0.len());
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nquery::NQuery;
+    use crate::testutil::powerset_graph;
+    use graphbench::{editgraph::EditGraph, graph::MutableGraph};
+
+    #[test]
+    fn heuristic_vc() {
+        let (G, v) = powerset_graph(3);
+        let D = DegenGraph::with_ordering(&G, (0..v).collect_vec().iter());
+
+        let mut alg = HeuristicAlgorithm::new(&D);
+        let S = alg.run_vc();
+        assert_eq!(S.len(), 3);
+
+        let mut nquery = NQuery::new(&D);
+        nquery.ensure_size(3);
+        assert!(nquery.is_shattered(&S));
+
+        let ladder = alg.run_ladder();
+        nquery.ensure_size(ladder.len());
+        assert!(!ladder.is_empty() && nquery.contains_ladder(&ladder));
+
+        // A single test only covers the first vertex of the first neighbourhood
+        alg.set_budget(1);
+        assert_eq!(alg.run_vc().len(), 1);
+    }
+
+    #[test]
+    fn heuristic_biclique() {
+        let mut G = EditGraph::new();
+        for u in 0..3 {
+            for v in 3..6 {
+                G.add_edge(&u, &v);
+            }
+        }
+        let D = DegenGraph::from_graph(&G);
+
+        let mut alg = HeuristicAlgorithm::new(&D);
+        let (S, T) = alg.run_biclique();
+        assert_eq!((S.len(), T.len()), (3, 3));
+        assert!(S.iter().cartesian_product(T.iter()).all(|(x, y)| D.adjacent(x, y)));
+    }
+}
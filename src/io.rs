@@ -1,8 +1,13 @@
 use std::path::Path;
 use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use fxhash::FxHashSet;
+use flate2::read::GzDecoder;
 
 use graphbench::editgraph::EditGraph;
-use graphbench::graph::VertexSet;
+use graphbench::graph::{Vertex, VertexSet, VertexMap, MutableGraph};
 
 pub fn load_graph(file:&Path) -> Result<EditGraph, String> {
     if !(file.exists() && file.is_file()) {
@@ -25,4 +30,116 @@ pub fn load_graph(file:&Path) -> Result<EditGraph, String> {
             Err(format!("Invalid file `{file:?}`. The supported formats are `.txt.gz` and `.txt`."))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Reads an edge list `u v [label]`, one edge per line. Empty lines and lines starting with `#`
+/// are skipped. Returns the edges in file order together with their labels, if present. If `labeled`
+/// is set, every edge must have a label.
+fn read_edge_list(file:&Path, labeled:bool) -> Result<Vec<(Vertex, Vertex, Option<String>)>, String> {
+    if !(file.exists() && file.is_file()) {
+        return Err(format!("The provided file `{file:?}` does not exist or is a directory."));
+    }
+
+    let handle = File::open(file).map_err(|e| format!("Could not open `{file:?}`: {e}"))?;
+    let reader:Box<dyn BufRead> = match file.extension().and_then(OsStr::to_str) {
+        Some("txt") => Box::new(BufReader::new(handle)),
+        Some("gz") => Box::new(BufReader::new(GzDecoder::new(handle))),
+        _ => return Err(format!("Invalid file `{file:?}`. The supported formats are `.txt.gz` and `.txt`."))
+    };
+    parse_edge_list(reader, file, labeled)
+}
+
+/// Parses the lines of an edge list as described in `read_edge_list`, `file` is only used in error messages.
+fn parse_edge_list(reader:impl BufRead, file:&Path, labeled:bool) -> Result<Vec<(Vertex, Vertex, Option<String>)>, String> {
+    let mut edges = Vec::default();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Could not read `{file:?}`: {e}"))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let tokens:Vec<_> = line.split_whitespace().collect();
+        let parse = |token:Option<&&str>| token.and_then(|t| t.parse::<Vertex>().ok());
+        match (parse(tokens.first()), parse(tokens.get(1))) {
+            (Some(u), Some(v)) if labeled && tokens.len() < 3 => {
+                return Err(format!("Edge {u} {v} in line {} of `{file:?}` has no label", i+1))
+            },
+            (Some(u), Some(v)) => edges.push((u, v, tokens.get(2).map(|l| l.to_string()))),
+            _ => return Err(format!("Could not parse edge in line {} of `{file:?}`", i+1))
+        }
+    }
+
+    Ok(edges)
+}
+
+/// Loads a directed graph from a list of arcs `u v`, one per line. Returns the underlying undirected
+/// graph together with the set of arcs, loops are ignored.
+pub fn load_digraph(file:&Path) -> Result<(EditGraph, FxHashSet<(Vertex, Vertex)>), String> {
+    let edges = read_edge_list(file, false)?;
+    Ok(digraph_from_edges(edges.iter().map(|(u, v, _)| (*u, *v))))
+}
+
+/// Builds the underlying undirected graph and the set of arcs from `arcs`, loops are ignored.
+pub fn digraph_from_edges(arcs:impl Iterator<Item=(Vertex, Vertex)>) -> (EditGraph, FxHashSet<(Vertex, Vertex)>) {
+    let mut graph = EditGraph::new();
+    let mut arc_set = FxHashSet::default();
+    for (u, v) in arcs {
+        if u != v {
+            graph.add_edge(&u, &v);
+            arc_set.insert((u, v));
+        }
+    }
+    (graph, arc_set)
+}
+
+/// Loads an edge list with an edge label in the third column, `u v label`. 
+pub fn load_labeled_edges(file:&Path) -> Result<Vec<(Vertex, Vertex, String)>, String> {
+    let edges = read_edge_list(file, true)?;
+    Ok(edges.into_iter().map(|(u, v, label)| (u, v, label.unwrap())).collect())
+}
+
+/// Writes `values` as a two-column CSV file with a header `vertex,{column}`, sorted by vertex.
+pub fn write_vertex_values(file:&Path, column:&str, values:&VertexMap<usize>) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(file)?);
+    writeln!(writer, "vertex,{column}")?;
+
+    let mut vertices:Vec<_> = values.keys().collect();
+    vertices.sort_unstable();
+    for v in vertices {
+        writeln!(writer, "{v},{}", values[v])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edge_list() {
+        let file = Path::new("edges.txt");
+        let input = "# comment\n0 1\n\n  2 3 knows \n4\t5\tlikes\n";
+
+        let edges = parse_edge_list(input.as_bytes(), file, false).unwrap();
+        assert_eq!(edges, vec![(0, 1, None), (2, 3, Some("knows".to_string())), (4, 5, Some("likes".to_string()))]);
+
+        // Labelled lists need a label on every line, the error names the line
+        let err = parse_edge_list(input.as_bytes(), file, true).unwrap_err();
+        assert!(err.contains("line 2"), "{err}");
+        let edges = parse_edge_list("2 3 knows\n# comment\n4 5 likes\n".as_bytes(), file, true).unwrap();
+        assert_eq!(edges.len(), 2);
+    }
+
+    #[test]
+    fn malformed_edge_list() {
+        let file = Path::new("edges.txt");
+        for input in ["0 1\n2\n", "0 1\na b\n", "0 1\n-1 2\n", "0 1\n0 x label\n"] {
+            let err = parse_edge_list(input.as_bytes(), file, false).unwrap_err();
+            assert!(err.contains("line 2"), "{err}");
+        }
+
+        assert!(read_edge_list(Path::new("edges.csv"), false).is_err());
+        assert!(load_labeled_edges(Path::new("does/not/exist.txt")).is_err());
+    }
+}
@@ -2,6 +2,11 @@
 #![allow(unused_variables)]
 #![allow(unused_mut)]
 #![allow(unused_imports)]
+// Lints triggered by the original modules and their tests, which are kept as they are
+#![allow(unused_parens)]
+#![allow(clippy::ptr_arg, clippy::map_clone, clippy::useless_conversion, clippy::iter_cloned_collect)]
+#![allow(clippy::bool_assert_comparison, clippy::explicit_counter_loop, clippy::needless_return)]
+#![allow(clippy::unnecessary_map_or, clippy::derivable_impls, clippy::unnecessary_cast, clippy::useless_vec)]
 
 mod io;
 mod nquery;
@@ -9,13 +14,27 @@ mod algorithms;
 mod setfunc;
 mod vecset;
 mod skipcombs;
+mod ordering;
+mod bicliques;
+mod bipartite;
+mod heuristic;
+mod twins;
+mod components;
+#[cfg(test)]
+mod testutil;
 
 // use std::backtrace::Backtrace;
 use std::collections::BTreeSet;
 use std::default;
-use io::load_graph;
+use io::{load_graph, load_digraph, load_labeled_edges, digraph_from_edges, write_vertex_values};
 use nquery::*;
 use algorithms::*;
+use bicliques::MaximalBicliques;
+use bipartite::two_colouring;
+use heuristic::HeuristicAlgorithm;
+use twins::TwinClasses;
+use components::{aggregate_components, induced_subgraph, Aggregation};
+use ordering::{linear_ordering, colouring_numbers, degree_ordering};
 
 use graphbench::editgraph::EditGraph;
 use graphbench::graph::*;
@@ -24,7 +43,7 @@ use graphbench::io::load_vertex_set;
 use graphbench::degengraph::*;
 use itertools::*;
 
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 
 use clap::{Parser, ValueEnum};
 use std::path::Path;
@@ -44,78 +63,671 @@ struct Args {
     /// The network file
     file:String,    
 
-    ///  (VC only) restrict search of shattered set to these vertices
+    ///  (VC) restrict search of shattered set to these vertices, all vertices still act as witnesses,
+    ///  (Ladder, crown, biclique) restrict the elements of a ladder or crown, or one side of a biclique, to these vertices,
+    ///  (Dual VC) the vertices whose neighbourhoods form the set system
     shattered_candidates:Option<String>,
+
+    /// Compute the statistic on the subgraph induced by the vertices in this file
+    #[clap(long)]
+    induce:Option<String>,
+
+    /// Use balls of this radius instead of neighbourhoods
+    #[clap(long, default_value = "1")]
+    radius:usize,
+
+    /// (VC only) list all shattered sets of maximum size
+    #[clap(long)]
+    all_witnesses:bool,
+
+    /// (VC only) stop listing shattered sets after this many
+    #[clap(long)]
+    witness_limit:Option<usize>,
+
+    /// (VC only) count the shattered sets of every size instead of computing the VC dimension
+    #[clap(long)]
+    shatter_profile:bool,
+
+    /// (VC only) count shattered sets up to this size exactly, estimate the counts of larger sizes
+    #[clap(long, default_value = "3")]
+    exact_up_to:usize,
+
+    /// Number of samples used for estimates
+    #[clap(long, default_value = "1000")]
+    samples:usize,
+
+    /// (VC only) write the largest shattered set inside each vertex' closed left neighbourhood to this CSV file
+    #[clap(long)]
+    local_csv:Option<String>,
+
+    /// (VC only) use closed neighbourhoods instead of closed left neighbourhoods for --local-csv
+    #[clap(long)]
+    local_ball:bool,
+
+    /// Read the file as a list of labelled edges `u v label` and only use edges with one of these comma-separated labels
+    #[clap(long)]
+    relation:Option<String>,
+
+    /// Read the file as a list of labelled edges `u v label` and compute the statistic separately for every relation
+    #[clap(long)]
+    per_relation:bool,
+
+    /// (VC, ladder, biclique) compute greedy lower bounds which test sets directly against the adjacency lists
+    #[clap(long)]
+    heuristic:bool,
+
+    /// (Heuristic only) maximum number of sets tested
+    #[clap(long, default_value = "100000")]
+    budget:usize,
+
+    /// Vertex ordering used by all algorithms, `degeneracy-or-degree` picks whichever of the two has the
+    /// smaller weak r-colouring number for r = max(1, --colouring-radius)
+    #[clap(long, value_enum, default_value = "degeneracy")]
+    ordering:OrderingArg,
+
+    /// Report the weak and strong r-colouring numbers of the ordering for all r up to this value
+    #[clap(long, default_value = "0")]
+    colouring_radius:usize,
+
+    /// Collapse classes of twins (false twins, or true twins with --closed) before computing the statistic
+    #[clap(long)]
+    twins:bool,
+
+    /// Compute the statistic separately for every connected component and report the maximum over all
+    /// components (the sum for induced matchings). Components which cannot beat the current maximum are skipped
+    #[clap(long)]
+    components:bool,
+
+    /// Use closed neighbourhoods N[v] (closed balls for --radius) as set system
+    #[clap(long)]
+    closed:bool,
+
+    /// (VC only) read the file as a list of arcs and use the out-neighbourhoods as set system
+    #[clap(long)]
+    directed:bool,
+
+    /// (VC only) with --directed, use the in-neighbourhoods instead of the out-neighbourhoods
+    #[clap(long)]
+    in_neighbourhoods:bool,
+
+    /// (VC only) file with the vertices which may realise traces, only their neighbourhoods form the set system
+    #[clap(long)]
+    witnesses:Option<String>,
+
+    /// (VC, dual VC, ladder) file with the vertices of one side of a bipartite graph, all other vertices form the 
+    /// other side. The statistic is computed for the neighbourhoods of the second side on the first side
+    #[clap(long)]
+    sides:Option<String>,
+
+    /// (VC, dual VC, ladder) like --sides but the sides are obtained from a 2-colouring of the graph
+    #[clap(long)]
+    bipartite:bool,
+
+    /// (VC, dual VC, ladder) exchange the two sides given by --sides or --bipartite
+    #[clap(long)]
+    swap_sides:bool,
+
+    /// (Biclique only) compute the largest t with K_{s,t} ⊆ G for every s up to this value
+    #[clap(long)]
+    unbalanced:Option<usize>,
+
+    /// (Biclique only) only search for induced bicliques, both sides have to be independent sets
+    #[clap(long)]
+    induced:bool,
+
+    /// (Complexity, VC density) largest set size k for which traces are counted
+    #[clap(long)]
+    max_size:Option<usize>,
+
+    /// (Complexity only) test all k-sets of the graph instead of only those in left neighbourhoods
+    #[clap(long)]
+    exact:bool,
+
+    /// (Maximal bicliques only) minimum size of the first side of a reported biclique
+    #[clap(long, default_value = "1")]
+    min_left:usize,
+
+    /// (Maximal bicliques only) minimum size of the second side of a reported biclique
+    #[clap(long, default_value = "1")]
+    min_right:usize,
 }
 
 #[derive(Clone, Debug, ValueEnum)]
 enum StatisticArg {
     VC, 
+    DualVC,
     Ladder,
+    SemiLadder,
     Crown,
-    Biclique
+    Biclique,
+    InducedMatching,
+    Complexity,
+    VCDensity,
+    MaximalBicliques
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+enum OrderingArg {
+    Degeneracy,
+    Degree,
+    DegeneracyOrDegree
 }
 
 fn main() -> Result<(), &'static str> {
     let args = Args::parse();
-    let filename = args.file;
+    let path = Path::new(&args.file);
+
+    if args.radius == 0 {
+        return Err("The radius must be at least 1");
+    }
+
+    if args.samples == 0 {
+        return Err("The number of samples must be positive");
+    }
+
+    if args.closed && (args.induced || matches!(args.statistic, StatisticArg::InducedMatching | StatisticArg::MaximalBicliques)) {
+        return Err("Closed neighbourhoods are not supported for statistics defined on the edges of the graph");
+    }
+
+    if args.radius > 1 && matches!(args.statistic, StatisticArg::InducedMatching | StatisticArg::MaximalBicliques) {
+        return Err("Balls of radius larger than one are not supported for statistics defined on the edges of the graph");
+    }
+
+    if (args.sides.is_some() || args.bipartite || args.swap_sides) && !matches!(args.statistic, StatisticArg::VC | StatisticArg::DualVC | StatisticArg::Ladder) {
+        return Err("Sides are only supported for the VC dimension, the dual VC dimension and the ladder index");
+    }
+
+    if args.heuristic && (args.closed || args.directed || args.radius > 1) {
+        return Err("Heuristic mode only supports open neighbourhoods of undirected graphs");
+    }
+
+    if args.twins && (args.directed || args.radius > 1 || args.local_csv.is_some() || args.shatter_profile || args.all_witnesses
+            || args.unbalanced.is_some() || args.induced
+            || matches!(args.statistic, StatisticArg::Complexity | StatisticArg::VCDensity | StatisticArg::MaximalBicliques)
+            || (args.closed && matches!(args.statistic, StatisticArg::Biclique))) {
+        return Err("Twin reduction is not supported for this statistic or combination of options");
+    }
+
+    if args.components && (args.heuristic || args.local_csv.is_some() || args.shatter_profile || args.all_witnesses
+            || args.unbalanced.is_some() || args.witnesses.is_some() || args.sides.is_some() || args.bipartite
+            || matches!(args.statistic, StatisticArg::DualVC | StatisticArg::Complexity | StatisticArg::VCDensity | StatisticArg::MaximalBicliques)) {
+        return Err("Component decomposition is not supported for this statistic or combination of options");
+    }
+
+    if args.directed && (!matches!(args.statistic, StatisticArg::VC) || args.radius > 1) {
+        return Err("Directed graphs are only supported for the VC dimension with radius 1");
+    }
 
     // Load graph
-    let path = Path::new(&filename);
-    let mut graph = match load_graph(path) {
-        Ok(G) => G,
-        Err(msg) => {
-            println!("{msg}");
-            return Err("Parsing error");
+    if args.relation.is_some() || args.per_relation {
+        let edges = match load_labeled_edges(path) {
+            Ok(edges) => edges,
+            Err(msg) => {
+                println!("{msg}");
+                return Err("Parsing error");
+            }
+        };
+
+        let vertices:BTreeSet<Vertex> = edges.iter().flat_map(|(u, v, _)| [*u, *v]).collect();
+        let labels:BTreeSet<&String> = edges.iter().map(|(_, _, label)| label).collect();
+        println!("Loaded {} labelled edges with {} relations", edges.len(), labels.len());
+
+        let selected:Option<BTreeSet<&str>> = args.relation.as_ref().map(|rels| rels.split(',').collect());
+        let runs:Vec<BTreeSet<&str>> = if args.per_relation {
+            labels.iter()
+                .filter(|label| selected.as_ref().is_none_or(|rels| rels.contains(label.as_str())))
+                .map(|label| [label.as_str()].into_iter().collect())
+                .collect()
+        } else {
+            selected.into_iter().collect()
+        };
+
+        for relations in runs {
+            println!("Relations {relations:?}");
+            let edges = edges.iter().filter(|(_, _, label)| relations.contains(label.as_str())).map(|(u, v, _)| (*u, *v));
+            let (mut graph, arcs) = digraph_from_edges(edges);
+            if graph.num_edges() == 0 {
+                println!("No edges with these relations, skipping");
+                continue;
+            }
+
+            // Vertices without edges of these relations still realise the empty trace
+            for v in &vertices {
+                graph.add_vertex(v);
+            }
+            run_statistic(&args, graph, if args.directed { Some(arcs) } else { None })?;
+        }
+        return Ok(());
+    }
+
+    let (graph, arcs) = if args.directed {
+        match load_digraph(path) {
+            Ok((G, arcs)) => {
+                println!("Loaded directed graph with {} arcs", arcs.len());
+                (G, Some(arcs))
+            },
+            Err(msg) => {
+                println!("{msg}");
+                return Err("Parsing error");
+            }
+        }
+    } else {
+        match load_graph(path) {
+            Ok(G) => (G, None),
+            Err(msg) => {
+                println!("{msg}");
+                return Err("Parsing error");
+            }
         }
     };
 
+    run_statistic(&args, graph, arcs)
+}
+
+/// Computes the statistic selected in `args` on `graph`, or on each of its connected components with 
+/// `--components`. If `arcs` is given, `graph` is the underlying undirected graph of a directed graph.
+fn run_statistic(args:&Args, mut graph:EditGraph, arcs:Option<FxHashSet<(Vertex, Vertex)>>) -> Result<(), &'static str> {
     println!("Loaded graph with n={} and m={}", graph.num_vertices(), graph.num_edges());
     
     graph.remove_loops();
-    let graph = DegenGraph::from_graph(&graph);  
+
+    if let Some(filename) = &args.induce {
+        let vertices:Vec<Vertex> = load_candidates(filename)?.into_iter().sorted_unstable().collect();
+        graph = induced_subgraph(&graph, &vertices);
+        if graph.num_vertices() == 0 {
+            return Err("None of the vertices to induce on are contained in the graph");
+        }
+        println!("Induced subgraph on {} vertices contained in `{filename}` has m={}", graph.num_vertices(), graph.num_edges());
+    }
+
+    let twins = if args.twins {
+        let twins = TwinClasses::new(&graph, args.closed);
+        println!("Collapsed {} vertices into {} twin classes", graph.num_vertices(), twins.num_classes());
+        graph = twins.reduce(&graph);
+        Some(twins)
+    } else {
+        None
+    };
+
+    if !args.components {
+        compute_statistic(args, &graph, &arcs, &twins)?;
+        return Ok(());
+    }
+
+    // In the whole graph the empty trace is realised by the vertices of the other components
+    let add_dummy = matches!(args.statistic, StatisticArg::VC);
+    let sum = matches!(args.statistic, StatisticArg::InducedMatching);
+    let aggregation = if sum { Aggregation::Sum } else { Aggregation::Max };
+
+    // The degeneracy is the smallest d over all orderings, so the bound holds for every ordering
+    let bound = |H:&EditGraph| {
+        let d = DegenGraph::from_graph(H).left_degrees().values().max().map_or(0, |d| *d as usize);
+        a_priori_bound(args, H.num_vertices(), d)
+    };
+    let (lower, upper, best_component) = aggregate_components(&graph, aggregation, add_dummy, bound, 
+        |H| compute_statistic(args, H, &arcs, &twins))?;
+
+    let name = match args.statistic {
+        StatisticArg::VC => "VC dimension",
+        StatisticArg::Ladder => "Ladder index",
+        StatisticArg::SemiLadder => "Semi-ladder index",
+        StatisticArg::Crown => "Crown size",
+        StatisticArg::Biclique => "Biclique size",
+        StatisticArg::InducedMatching => "Induced matching number",
+        _ => unreachable!()
+    };
+    let combined = if sum { "sum" } else { "maximum" };
+    if args.radius > 1 {
+        println!("{name} of the whole graph ({combined} over all components) is at least {lower}");
+    } else if lower == upper {
+        println!("{name} of the whole graph ({combined} over all components) is {lower}");
+    } else {
+        println!("{name} of the whole graph ({combined} over all components) is between {lower} and {upper}");
+    }
+    if let Some(i) = best_component {
+        println!("Largest value found in component {i}");
+    }
+
+    Ok(())
+}
+
+/// Computes the statistic selected in `args` on `graph` and returns its lower and upper bound, if the 
+/// statistic provides them.
+fn compute_statistic(args:&Args, graph:&EditGraph, arcs:&Option<FxHashSet<(Vertex, Vertex)>>, 
+        twins:&Option<TwinClasses>) -> Result<Option<(usize, usize)>, &'static str> {
+    // Vertex sets from files refer to the original graph, twins are replaced by their representatives
+    let load_vertices = |filename:&str| -> Result<VertexSet, &'static str> {
+        let set = load_candidates(filename)?;
+        Ok(match &twins {
+            Some(twins) => twins.reduce_set(&set),
+            None => set
+        })
+    };
+
+    // Colouring numbers (r, wcol_r, col_r) of the chosen ordering which were already computed
+    let (graph, ordering_name, known_colouring) = match args.ordering {
+        OrderingArg::Degeneracy => (DegenGraph::from_graph(graph), "degeneracy", None),
+        OrderingArg::Degree => (DegenGraph::with_ordering(graph, degree_ordering(graph).iter()), "degree", None),
+        OrderingArg::DegeneracyOrDegree => {
+            // Pick the ordering with the smaller weak colouring number for the largest reported radius
+            let r = std::cmp::max(args.colouring_radius, 1);
+            let degen = DegenGraph::from_graph(graph);
+            let degree = DegenGraph::with_ordering(graph, degree_ordering(graph).iter());
+            let (wcol_degen, col_degen) = colouring_numbers(&degen, &linear_ordering(&degen), r);
+            let (wcol_degree, col_degree) = colouring_numbers(&degree, &linear_ordering(&degree), r);
+            println!("Weak {r}-colouring numbers: {wcol_degen} (degeneracy ordering), {wcol_degree} (degree ordering)");
+            if wcol_degree < wcol_degen {
+                (degree, "degree", Some((r, wcol_degree, col_degree)))
+            } else {
+                (degen, "degeneracy", Some((r, wcol_degen, col_degen)))
+            }
+        }
+    };
 
     let d = *graph.left_degrees().values().max().unwrap() as usize;
     let logd = (d as f32).log2();    
-    println!("Computed degeneracy ordering with d={} (log d = {:.2})", d, logd);
 
-    match args.statistic {
+    println!("Computed {} ordering with d={} (log d = {:.2})", ordering_name, d, logd);
+
+    let order = linear_ordering(&graph);
+    for r in 1..=args.colouring_radius {
+        let (wcol, col) = match known_colouring {
+            Some((known_r, wcol, col)) if known_r == r => (wcol, col),
+            _ => colouring_numbers(&graph, &order, r)
+        };
+        println!("  wcol_{r} = {wcol}, col_{r} = {col}");
+    }
+
+    if args.heuristic {
+        let mut alg = HeuristicAlgorithm::new(&graph);
+        alg.set_budget(args.budget);
+        match &args.statistic {
+            StatisticArg::VC => { alg.run_vc(); },
+            StatisticArg::Ladder => { alg.run_ladder(); },
+            StatisticArg::Biclique => { alg.run_biclique(); },
+            _ => return Err("Heuristic mode is only available for VC, ladder and biclique")
+        }
+        return Ok(None);
+    }
+
+    // Sides of a bipartite graph: the first side is the ground set, the neighbourhoods of the second side form the sets
+    let sides = if let Some(filename) = &args.sides {
+        let mut first = load_vertices(filename)?;
+        first.retain(|v| graph.contains(v));
+        let second:VertexSet = graph.vertices().filter(|v| !first.contains(v)).cloned().collect();
+        Some((first, second))
+    } else if args.bipartite {
+        match two_colouring(&graph) {
+            Some(first) => {
+                let second:VertexSet = graph.vertices().filter(|v| !first.contains(v)).cloned().collect();
+                Some((first, second))
+            },
+            None => return Err("Graph is not bipartite")
+        }
+    } else {
+        None
+    };
+    let sides = sides.map(|(first, second)| if args.swap_sides { (second, first) } else { (first, second) });
+    if let Some((first, second)) = &sides {
+        println!("Using sides with {} and {} vertices", first.len(), second.len());
+    }
+
+    let bounds = match &args.statistic {
         StatisticArg::VC => {
             println!("Computing VC dimension");
             let mut alg = VCAlgorithm::new(&graph);
+            alg.set_closed(args.closed);
+            alg.set_radius(args.radius);
 
-            if let Some(filename) = args.shattered_candidates {
-                let cand_set = match load_vertex_set(&filename) {
-                    Ok(cand_set) => cand_set,
-                    Err(error) => {
-                        println!("{:?}", error);
-                        return Err("Could not parse candidate vertex set");
-                    }
-                };
+            if let Some(arcs) = &arcs {
+                let orientation = if args.in_neighbourhoods { Orientation::In } else { Orientation::Out };
+                println!("Using the {orientation:?}-neighbourhoods as set system");
+                alg.set_arcs(arcs, orientation);
+            }
+
+            if let Some((ground, sets)) = &sides {
+                alg.set_sides(ground, sets);
+            }
+
+            if let Some(filename) = &args.shattered_candidates {
+                let mut cand_set = load_vertices(filename)?;
+                if let Some((ground, _)) = &sides {
+                    cand_set.retain(|v| ground.contains(v));
+                }
                 let cand_size = cand_set.len();
                 println!("Restricting VC search to {cand_size} vertices contained in `{filename}`");
                 alg.set_shatter_candidates(&cand_set);
             }
 
+            if let Some(filename) = &args.witnesses {
+                let mut witness_set = load_vertices(filename)?;
+                if let Some((_, sets)) = &sides {
+                    witness_set.retain(|v| sets.contains(v));
+                }
+                println!("Restricting witnesses to {} vertices contained in `{filename}`", witness_set.len());
+                alg.set_witness_candidates(&witness_set);
+            }
+
+            if let Some(filename) = &args.local_csv {
+                println!("Computing local VC dimensions");
+                let local_vc = alg.local_vc_dimensions(args.local_ball);
+                if let Err(error) = write_vertex_values(Path::new(filename), "local_vc", &local_vc) {
+                    println!("{:?}", error);
+                    return Err("Could not write local VC dimensions");
+                }
+                println!("Wrote local VC dimensions to `{filename}`");
+                return Ok(None);
+            }
+
+            if args.shatter_profile {
+                println!("Computing shatter profile");
+                alg.shatter_profile(args.exact_up_to, args.samples);
+                return Ok(None);
+            }
+
             alg.run();            
+
+            if args.all_witnesses {
+                println!("Listing all shattered sets of size {}", alg.vc_dim());
+                alg.enumerate_witnesses(args.witness_limit);
+            }
+            Some((alg.vc_dim(), alg.vc_dim()))
+        },
+        StatisticArg::DualVC => {
+            println!("Computing dual VC dimension");
+            if let Some((ground, sets)) = &sides {
+                if args.shattered_candidates.is_some() {
+                    return Err("A set file cannot be combined with --sides or --bipartite");
+                }
+
+                // The dual system has the second side as ground set and the neighbourhoods of the first side as sets
+                let mut alg = DualVCAlgorithm::new(&graph, sets);
+                alg.set_witness_candidates(ground);
+                alg.set_closed(args.closed);
+                alg.set_radius(args.radius);
+                alg.run();
+                return Ok(None);
+            }
+
+            let sets = if let Some(filename) = &args.shattered_candidates {
+                let sets = load_vertices(filename)?;
+                println!("Set system consists of the neighbourhoods of the {} vertices contained in `{filename}`", sets.len());
+                sets
+            } else {
+                println!("No set file given: every neighbourhood is a set, so the dual VC dimension equals the VC dimension");
+                graph.vertices().cloned().collect()
+            };
+
+            let mut alg = DualVCAlgorithm::new(&graph, &sets);
+            alg.set_closed(args.closed);
+            alg.set_radius(args.radius);
+            alg.run();
+            Some((alg.vc_dim(), alg.vc_dim()))
         },
         StatisticArg::Ladder => {
             println!("Approximating ladder index");
             let mut alg = LadderAlgorithm::new(&graph);
+            alg.set_closed(args.closed);
+            alg.set_radius(args.radius);
+
+            if let Some((ground, sets)) = &sides {
+                alg.set_sides(ground, sets);
+            }
+
+            if let Some(filename) = &args.shattered_candidates {
+                let mut candidates = load_vertices(filename)?;
+                if let Some((ground, _)) = &sides {
+                    candidates.retain(|v| ground.contains(v));
+                }
+                println!("Restricting ladder search to {} vertices contained in `{filename}`", candidates.len());
+                alg.set_candidates(&candidates);
+            }
+
             alg.run();   
+            Some(alg.bounds())
+        },
+        StatisticArg::SemiLadder => {
+            println!("Approximating semi-ladder index");
+            let mut alg = SemiLadderAlgorithm::new(&graph);
+            alg.set_closed(args.closed);
+            alg.set_radius(args.radius);
+            alg.run();   
+            Some(alg.bounds())
         },
         StatisticArg::Crown => {
             println!("Approximating crown size");
             let mut alg = CrownAlgorithm::new(&graph);
+            alg.set_closed(args.closed);
+            alg.set_radius(args.radius);
+
+            if let Some(filename) = &args.shattered_candidates {
+                let candidates = load_vertices(filename)?;
+                println!("Restricting crown search to {} vertices contained in `{filename}`", candidates.len());
+                alg.set_candidates(&candidates);
+            }
+
             alg.run();               
+            Some(alg.bounds())
         },
         StatisticArg::Biclique => {
             println!("Computing biclique size");
             let mut alg = BicliqueAlgorithm::new(&graph);
+            alg.set_closed(args.closed);
+            alg.set_radius(args.radius);
+            alg.set_induced(args.induced);
+
+            if let Some(filename) = &args.shattered_candidates {
+                if args.unbalanced.is_some() || twins.is_some() {
+                    return Err("A candidate file cannot be combined with --unbalanced or --twins");
+                }
+                let candidates = load_vertices(filename)?;
+                println!("Restricting one side of the biclique to {} vertices contained in `{filename}`", candidates.len());
+                alg.set_candidates(&candidates);
+            }
+
+            if let Some(max_s) = args.unbalanced {
+                alg.run_unbalanced(max_s);
+                return Ok(None);
+            }
+
+            if let Some(twins) = &twins {
+                // Every representative stands for its whole class
+                let Some(size) = alg.run_weighted(&twins.multiplicities()) else {
+                    return Ok(None);
+                };
+                if let Some((A, B)) = alg.witness() {
+                    let S = twins.lift(A).into_iter().take(size).collect_vec();
+                    let T = twins.lift(B).into_iter().take(size).collect_vec();
+                    println!("Largest biclique found: {S:?} x {T:?}");
+                }
+                return Ok(Some((size, size)));
+            }
+
             alg.run();               
-        }        
+
+            if let Some((S, T)) = alg.witness() {
+                let name = if args.induced { "induced biclique" } else { "biclique" };
+                println!("Largest {name} found: {S:?} x {T:?}");
+            }
+            Some(alg.bounds())
+        },
+        StatisticArg::InducedMatching => {
+            println!("Approximating induced matching number");
+            let mut alg = InducedMatchingAlgorithm::new(&graph);
+            alg.run();
+            Some(alg.bounds())
+        },
+        StatisticArg::Complexity => {
+            println!("Computing neighbourhood complexity");
+            let mut alg = ComplexityAlgorithm::new(&graph);
+            alg.set_closed(args.closed);
+            alg.set_radius(args.radius);
+            if let Some(max_size) = args.max_size {
+                alg.set_max_size(max_size);
+            }
+            alg.set_exact(args.exact);
+            alg.run();
+            None
+        },
+        StatisticArg::VCDensity => {
+            println!("Estimating VC density");
+            let mut alg = VCDensityAlgorithm::new(&graph);
+            alg.set_closed(args.closed);
+            alg.set_radius(args.radius);
+            if let Some(max_size) = args.max_size {
+                alg.set_max_size(max_size);
+            }
+            alg.set_samples(args.samples);
+            alg.run();
+            None
+        },
+        StatisticArg::MaximalBicliques => {
+            println!("Enumerating maximal bicliques with sides of size at least {} and {}", args.min_left, args.min_right);
+            let mut count = 0;
+            for (A, B) in MaximalBicliques::new(&graph, args.min_left, args.min_right) {
+                println!("{A:?} x {B:?}");
+                count += 1;
+            }
+            println!("Found {count} maximal bicliques");
+            None
+        }
+    };
+
+    Ok(bounds)
+}
+
+/// Upper bound on the statistic selected in `args` for a graph with `n` vertices whose ordering has
+/// maximum left degree `d`. These are the bounds the algorithms start from, computed before any
+/// index is built so that hopeless components can be skipped.
+fn a_priori_bound(args:&Args, n:usize, d:usize) -> Option<usize> {
+    // A shattered set of size k needs 2^k distinct neighbourhoods
+    let log_bound = (n as f64).log2().floor() as usize;
+    if args.radius > 1 {
+        return match args.statistic {
+            StatisticArg::VC => Some(log_bound),
+            _ => None
+        };
     }
 
+    let d = d + usize::from(args.closed);
+    match args.statistic {
+        StatisticArg::VC => Some(std::cmp::min(d+1, log_bound)),
+        StatisticArg::Ladder => Some(std::cmp::min(2*d+1, n)),
+        StatisticArg::SemiLadder => Some(std::cmp::min(4*d+1, n)),
+        StatisticArg::Crown => Some(std::cmp::min(d+1, n)),
+        // Twin classes count with their multiplicity, so the reduced graph does not bound the size
+        StatisticArg::Biclique if args.twins => None,
+        StatisticArg::Biclique => Some(d),
+        _ => None
+    }
+}
 
-    Ok(())
-}
\ No newline at end of file
+fn load_candidates(filename:&str) -> Result<VertexSet, &'static str> {
+    match load_vertex_set(filename) {
+        Ok(cand_set) => Ok(cand_set),
+        Err(error) => {
+            println!("{:?}", error);
+            Err("Could not parse candidate vertex set")
+        }
+    }
+}
@@ -2,16 +2,31 @@ use graphbench::graph::*;
 use graphbench::degengraph::DegenGraph;
 
 use std::collections::BTreeSet;
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 
 use itertools::*;
 
 use crate::{setfunc::{SetFunc, SmallSetFunc}, vecset::{difference, union, intersection}};
+use crate::ordering::{linear_ordering, weak_reachability, ReachabilityMap};
+
+/// Which neighbourhoods of a directed graph form the set system
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Out,
+    In
+}
 
 pub struct NQuery<'a> {
     R:SetFunc,
     max_query_size: usize,
     degeneracy: usize,
+    radius: usize,
+    witnesses: Option<VertexSet>,
+    wreach: ReachabilityMap,
+    wreach_inv: ReachabilityMap,
+    balls: VertexMap<VertexSet>,
+    arcs: Option<(FxHashSet<(Vertex, Vertex)>, Orientation)>,
+    closed: bool,
     graph:&'a DegenGraph
 }
 
@@ -20,24 +35,167 @@ impl<'a> NQuery<'a> {
         let mut R = SetFunc::default();
         let degeneracy = *graph.left_degrees().values().max().unwrap() as usize;
 
-        NQuery { R, graph, max_query_size: 0, degeneracy }
+        NQuery { R, graph, max_query_size: 0, degeneracy, radius: 1, witnesses: None, wreach: VertexMap::default(), wreach_inv: VertexMap::default(), balls: VertexMap::default(), arcs: None, closed: false }
+    }
+
+    /// Answer queries for the set system of balls of radius `radius` (without their centres unless
+    /// `set_closed` is used) instead of the set system of neighbourhoods.
+    /// 
+    /// For radius larger than one we do not materialize the power graph, whose degeneracy can 
+    /// be much larger than that of the graph. Instead we use that dist(u,v) <= r iff there is
+    /// a vertex w which is weakly r-reachable from both u and v such that the two distances add up to 
+    /// at most r. The balls are computed once from the weakly reachable sets and kept, traces are
+    /// then computed directly from the balls.
+    pub fn set_radius(&mut self, radius:usize) {
+        assert!(radius >= 1);
+        assert!(radius == 1 || self.arcs.is_none(), "Balls are not supported for directed graphs");
+        self.radius = radius;
+        self.R = SetFunc::default();
+        self.max_query_size = 0;
+
+        if radius > 1 {
+            println!("Computing weakly {radius}-reachable sets...");
+            let order = linear_ordering(self.graph);
+            let (wreach, wreach_inv) = weak_reachability(self.graph, &order, radius);
+            self.wreach = wreach;
+            self.wreach_inv = wreach_inv;
+
+            let wcol = self.wreach.values().map(|W| W.len()).max().unwrap_or(0);
+            println!("Largest weakly {radius}-reachable set has size {wcol}");
+
+            self.balls = self.graph.vertices().map(|v| (*v, self.compute_ball(v))).collect();
+            let max_ball = self.balls.values().map(|B| B.len()).max().unwrap_or(0);
+            println!("Largest {radius}-ball has size {max_ball}");
+        } else {
+            self.wreach = VertexMap::default();
+            self.wreach_inv = VertexMap::default();
+            self.balls = VertexMap::default();
+        }
+    }
+
+    /// Only vertices in `witnesses` are counted when computing traces, e.g. the answer to
+    /// queries is with respect to the set system {N(v) : v ∈ witnesses}.
+    pub fn set_witness_candidates(&mut self, witnesses:&VertexSet) {
+        self.witnesses = Some(witnesses.iter().filter(|x| self.graph.contains(x)).cloned().collect());
+        self.R = SetFunc::default();
+        self.max_query_size = 0;
+    }
+
+    /// Answer queries for the set system of out- or in-neighbourhoods of a directed graph whose
+    /// underlying undirected graph is `graph`. Every arc (u,v) must be an edge of `graph`.
+    ///
+    /// The index R is then built over the left out- (in-) neighbourhoods, the left-neighbour 
+    /// correction in `prepare` adds those vertices of S which lie in the out- (in-) neighbourhood
+    /// of a witness to its right.
+    pub fn set_arcs(&mut self, arcs:&FxHashSet<(Vertex, Vertex)>, orientation:Orientation) {
+        assert_eq!(self.radius, 1, "Balls are not supported for directed graphs");
+        self.arcs = Some((arcs.clone(), orientation));
+        self.R = SetFunc::default();
+        self.max_query_size = 0;
+    }
+
+    /// Answer queries for the set system of closed neighbourhoods N[v] (or closed balls) instead.
+    ///
+    /// This is the open set system of the graph in which every vertex has a loop, so R is built 
+    /// over the closed left neighbourhoods. These have size at most d+1, and the left-neighbour
+    /// correction in `prepare` has to include the witness itself.
+    pub fn set_closed(&mut self, closed:bool) {
+        self.closed = closed;
+        self.R = SetFunc::default();
+        self.max_query_size = 0;
+    }
+
+    /// Size of the largest set over which R is built, queries up to this size are always exact.
+    pub fn max_left_size(&self) -> usize {
+        self.degeneracy + usize::from(self.closed)
+    }
+
+    /// Tests whether the vertex `u` is contained in the set of `w`, where `u` is either 
+    /// a neighbour of `w` or `w` itself. 
+    #[inline]
+    fn is_member(&self, u:&Vertex, w:&Vertex) -> bool {
+        if u == w {
+            return self.closed;
+        }
+        match &self.arcs {
+            None => true,
+            Some((arcs, Orientation::Out)) => arcs.contains(&(*w, *u)),
+            Some((arcs, Orientation::In)) => arcs.contains(&(*u, *w)),
+        }
+    }
+
+    /// Size of the set of `w`, e.g. its degree for undirected graphs.
+    fn set_size(&self, w:&Vertex) -> usize {
+        let size = if self.arcs.is_none() {
+            self.graph.degree(w) as usize
+        } else {
+            self.graph.neighbours(w).filter(|u| self.is_member(u, w)).count()
+        };
+        size + usize::from(self.closed)
+    }
+
+    #[inline]
+    fn is_witness(&self, v:&Vertex) -> bool {
+        self.witnesses.as_ref().is_none_or(|W| W.contains(v))
+    }
+
+    /// Number of sets in the set system
+    pub fn num_witnesses(&self) -> usize {
+        self.witnesses.as_ref().map_or(self.graph.num_vertices(), |W| W.len())
+    }
+
+    pub fn radius(&self) -> usize {
+        self.radius
+    }
+
+    /// Returns the closed left neighbourhood of `v` or, for larger radii, the set of vertices
+    /// weakly reachable from `v`. These are the sets in which search algorithms look for structures.
+    pub fn left_ball(&self, v:&Vertex) -> Vec<Vertex> {
+        if self.radius == 1 {
+            let mut N = self.graph.left_neighbours(v);
+            N.push(*v);
+            N
+        } else {
+            self.wreach[v].iter().map(|(w,_)| *w).collect()
+        }
     }
 
-    fn query_uncor(&self, X: &Vec<Vertex>, S: &Vec<Vertex>) -> i32 {
-        if X.is_empty() {
-            return 0
+    /// Returns the set of `w` in the set system, e.g. N[w] with `set_closed`, the out-neighbourhood
+    /// of `w` with `set_arcs` or the ball around `w` for larger radii. Vertices which are not
+    /// witnesses have no set.
+    pub fn set_of(&self, w:&Vertex) -> Vec<Vertex> {
+        if !self.is_witness(w) {
+            return Vec::default();
         }
+        if self.radius > 1 {
+            return self.ball(w).into_iter().filter(|u| u != w || self.closed).collect();
+        }
+        self.graph.neighbours(w).chain(std::iter::once(w)).filter(|u| self.is_member(u, w)).cloned().collect()
+    }
 
-        let S_minus_X:Vec<_> = difference(S, X);
-        let mut res:i32 = 0;
+    /// Size of the largest set returned by `left_ball`
+    pub fn max_left_ball(&self) -> usize {
+        self.graph.vertices().map(|v| self.left_ball(v).len()).max().unwrap_or(0)
+    }
 
-        for subset in S_minus_X.into_iter().powerset() {
-            let Y = union(X, &subset);
+    /// Returns all vertices at distance at most `radius` from `v`, including `v` itself.
+    pub fn ball(&self, v:&Vertex) -> VertexSet {
+        if self.radius == 1 {
+            let mut res:VertexSet = self.graph.neighbours(v).cloned().collect();
+            res.insert(*v);
+            return res;
+        }
+        self.balls[v].clone()
+    }
 
-            if subset.len() % 2 == 0 {
-                res += self.R[&Y];
-            } else {
-                res -= self.R[&Y];
+    /// Computes the ball of radius `radius` around `v` from the weakly reachable sets.
+    fn compute_ball(&self, v:&Vertex) -> VertexSet {
+        let mut res = VertexSet::default();
+        for (w, dv) in self.wreach[v].iter() {
+            for (u, du) in self.wreach_inv[w].iter() {
+                if (dv + du) as usize <= self.radius {
+                    res.insert(*u);
+                }
             }
         }
         res
@@ -55,7 +213,11 @@ impl<'a> NQuery<'a> {
     }
 
     pub fn ensure_size(&mut self, size:usize) {
-        if size <= self.max_query_size || self.max_query_size == self.degeneracy {
+        if self.radius > 1 {
+            return; // Traces are computed directly from the weakly reachable sets
+        }
+
+        if size <= self.max_query_size || self.max_query_size == self.max_left_size() {
             return;
         }
 
@@ -63,7 +225,15 @@ impl<'a> NQuery<'a> {
 
         for s in (self.max_query_size+1)..=size {
             for u in self.graph.vertices() {
+                if !self.is_witness(u) {
+                    continue;
+                }
+
                 let mut N = self.graph.left_neighbours(u);
+                N.retain(|x| self.is_member(x, u));
+                if self.closed {
+                    N.push(*u);
+                }
                 N.sort_unstable();
     
                 for subset in N.into_iter().combinations(s) {
@@ -78,7 +248,11 @@ impl<'a> NQuery<'a> {
     /// Preparse the internal neighbourhood-data structure for queries of size `size`
     /// restricted to vertices in the set `query_candidates`.
     pub fn ensure_size_restricted(&mut self, size:usize, query_candidates:&VertexSet) {
-        if size <= self.max_query_size || self.max_query_size == self.degeneracy {
+        if self.radius > 1 {
+            return; // Traces are computed directly from the weakly reachable sets
+        }
+
+        if size <= self.max_query_size || self.max_query_size == self.max_left_size() {
             return;
         }
 
@@ -86,8 +260,15 @@ impl<'a> NQuery<'a> {
 
         for s in (self.max_query_size+1)..=size {
             for u in self.graph.vertices() {
+                if !self.is_witness(u) {
+                    continue;
+                }
+
                 let mut N = self.graph.left_neighbours(u);
-                N.retain(|x| query_candidates.contains(x));
+                if self.closed {
+                    N.push(*u);
+                }
+                N.retain(|x| query_candidates.contains(x) && self.is_member(x, u));
                 N.sort_unstable();
     
                 for subset in N.into_iter().combinations(s) {
@@ -102,9 +283,13 @@ impl<'a> NQuery<'a> {
     /// Preparse the vertex set S for neighbourhood-queries, e.g. for each subset X of S
     /// we obtain the number of vertices in G which have all of X as neighbours an none of S\X.
     fn prepare(&self,  S: &[Vertex]) -> SmallSetFunc {
+        if self.radius > 1 {
+            return self.prepare_distance(S);
+        }
+
         let mut S:Vec<u32> = S.iter().cloned().collect();
         S.sort_unstable();
-        assert!(S.len() <= self.max_query_size || self.max_query_size == self.degeneracy);
+        assert!(S.len() <= self.max_query_size || self.max_query_size == self.max_left_size());
 
         // Copies R into I on S. At this point, I[X] with X nonempty tells us how many vertices in G exist which
         // a) Are to the right of X in the ordering
@@ -119,16 +304,27 @@ impl<'a> NQuery<'a> {
         // We now insert the correct value for the empty set manually. Note that this
         // has to happend before we apply the 'left correction'.
         let res_sum:i32 = I.values_nonzero().sum();
-        I[&vec![]] = self.graph.num_vertices() as i32 - res_sum;
+        I[&vec![]] = self.num_witnesses() as i32 - res_sum;
 
         // Apply left-neighbour correction. Afterwards, I[X] tells us how many vertices in G exist
         // which have all of X as neigbhours and none of S/X
         let left_neighs = self.left_neighbour_set(&S);
 
         for v in left_neighs {   
-            // Collect v's left and right neighbourhoods 
-            let N: Vec<Vertex> = self.graph.neighbours(&v).cloned().sorted_unstable().collect();
-            let N_left: Vec<Vertex> = self.graph.left_neighbours(&v).into_iter().sorted_unstable().collect();
+            if !self.is_witness(&v) {
+                continue;
+            }
+
+            // Collect v's left and right neighbourhoods (restricted to the set of v for directed graphs).
+            // In the closed setting v belongs to its left neighbourhood, matching the sets in R.
+            let mut N: Vec<Vertex> = self.graph.neighbours(&v).filter(|x| self.is_member(x, &v)).cloned().collect();
+            let mut N_left: Vec<Vertex> = self.graph.left_neighbours(&v).into_iter().filter(|x| self.is_member(x, &v)).collect();
+            if self.closed {
+                N.push(v);
+                N_left.push(v);
+            }
+            N.sort_unstable();
+            N_left.sort_unstable();
             let N_right = difference(&N, &N_left);
             
             // Take the intersections of the neighbourhoods with S
@@ -141,7 +337,33 @@ impl<'a> NQuery<'a> {
             I[&N_left] -= 1;
             I[&N] += 1;
         }
-        assert_eq!(I.values_nonzero().sum::<i32>(), self.graph.num_vertices() as i32);
+        assert_eq!(I.values_nonzero().sum::<i32>(), self.num_witnesses() as i32);
+        I
+    }
+
+    /// Returns the number of distinct traces N(v) ∩ S over all vertices v of the graph.
+    pub fn count_traces(&self, S: &[Vertex]) -> usize {
+        let I = self.prepare(S);
+        I.count_nonzero()
+    }
+
+    /// Variant of `prepare` for radius larger than one. We collect the trace of every vertex
+    /// in the r-ball around S explicitly, all other vertices have an empty trace.
+    fn prepare_distance(&self, S: &[Vertex]) -> SmallSetFunc {
+        let mut traces:VertexMap<Vec<Vertex>> = VertexMap::default();
+        for s in S {
+            for u in self.balls[s].iter() {
+                if (u != s || self.closed) && self.is_witness(u) {
+                    traces.entry(*u).or_default().push(*s);
+                }
+            }
+        }
+
+        let mut I = SmallSetFunc::new(S);
+        for X in traces.values() {
+            I[X] += 1;
+        }
+        I[&vec![]] = (self.num_witnesses() - traces.len()) as i32;
         I
     }
 
@@ -158,6 +380,16 @@ impl<'a> NQuery<'a> {
         I.is_ladder()
     }
 
+    pub fn contains_semi_ladder(&self, S: &[Vertex]) -> bool {
+        let I = self.prepare(S);
+        I.is_semi_ladder()
+    }
+
+    pub fn contains_induced_matching(&self, S: &[Vertex]) -> bool {
+        let I = self.prepare(S);
+        I.contains_induced_matching()
+    }
+
     pub fn contains_crown(&self, S: &[Vertex]) -> bool {
         let I = self.prepare(S);
         I.contains_crown()
@@ -168,10 +400,48 @@ impl<'a> NQuery<'a> {
         I.contains_biclique()
     }    
 
+    /// Returns all sets of size `size` contained in a left neighbourhood together with the
+    /// number of witnesses to their right which see the whole set. 
+    pub fn left_sets(&self, size:usize) -> impl Iterator<Item=(&Vec<Vertex>, i32)> + '_ {
+        assert!(self.radius == 1 && (size <= self.max_query_size || self.max_query_size == self.max_left_size()));
+        self.R.entries_nonzero().filter(move |(S,_)| S.len() == size)
+    }
+
+    /// Returns all witnesses whose neighbourhood (or ball) contains all of `S`, sorted.
+    pub fn common_neighbours(&self, S: &[Vertex]) -> Vec<Vertex> {
+        if S.is_empty() {
+            return self.graph.vertices().filter(|v| self.is_witness(v)).cloned().sorted_unstable().collect();
+        }
+
+        let mut res:Vec<Vertex> = if self.radius == 1 {
+            self.graph.neighbours(&S[0]).chain(std::iter::once(&S[0]))
+                .filter(|u| S.iter().all(|s| (s == *u || self.graph.adjacent(u, s)) && self.is_member(s, u)))
+                .cloned().collect()
+        } else {
+            let mut common = self.ball(&S[0]);
+            for s in &S[1..] {
+                let ball = self.ball(s);
+                common.retain(|u| ball.contains(u));
+            }
+            common.into_iter().filter(|u| self.closed || !S.contains(u)).collect()
+        };
+
+        res.retain(|u| self.is_witness(u));
+        res.sort_unstable();
+        res
+    }
+
     pub fn degree_profile(&self, v:&Vertex) -> Vec<usize> {
+        if self.radius > 1 {
+            // Computing the sizes of all balls is too expensive, we use n as an upper bound
+            // for every vertex in the ball around v.
+            let witnesses = self.ball(v).into_iter().filter(|u| (u != v || self.closed) && self.is_witness(u)).count();
+            return vec![self.graph.num_vertices(); witnesses];
+        }
+
         let mut degrees = Vec::default();
-        for u in self.graph.neighbours(v) {
-            degrees.push(self.graph.degree(u) as usize);
+        for u in self.graph.neighbours(v).chain(std::iter::once(v)).filter(|u| self.is_witness(u) && self.is_member(v, u)) {
+            degrees.push(self.set_size(u));
         }
         degrees.sort_unstable();
         degrees.reverse();
@@ -186,6 +456,7 @@ mod  tests {
     use graphbench::{editgraph::EditGraph, graph::MutableGraph};
     use rand::prelude::*;
     use std::collections::BTreeSet;
+    use crate::testutil::powerset_graph;
 
     #[test]
     fn shattered_test1 () {
@@ -248,6 +519,125 @@ mod  tests {
         }
     }
 
+    #[test]
+    fn witness_restriction() {
+        let k = 3;
+        let (G, v) = powerset_graph(k);
+        let D = DegenGraph::from_graph(&G);
+        let S = (0..k).collect_vec();
+
+        let mut witnesses:VertexSet = (k..v).collect();
+        let mut nquery = NQuery::new(&D);
+        nquery.set_witness_candidates(&witnesses);
+        nquery.ensure_size(k as usize);
+        assert!(nquery.is_shattered(&S));
+
+        witnesses.remove(&(v-1));
+        nquery.set_witness_candidates(&witnesses);
+        nquery.ensure_size(k as usize);
+        assert!(!nquery.is_shattered(&S));
+        assert!(nquery.is_shattered(&S[..2]));
+    }
+
+    #[test]
+    fn directed_traces() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let n = 12;
+        let mut G = EditGraph::new();
+        let mut arcs:FxHashSet<(Vertex, Vertex)> = FxHashSet::default();
+        for u in 0..n {
+            for v in 0..n {
+                if u != v && rng.gen_bool(0.25) {
+                    G.add_edge(&u, &v);
+                    arcs.insert((u, v));
+                }
+            }
+        }
+
+        let D = DegenGraph::from_graph(&G);
+        let vertices = D.vertices().cloned().sorted_unstable().collect_vec();
+
+        for orientation in [Orientation::Out, Orientation::In] {
+            let mut nquery = NQuery::new(&D);
+            nquery.set_arcs(&arcs, orientation);
+            nquery.ensure_size(3);
+
+            for S in vertices.iter().cloned().combinations(3) {
+                let traces:BTreeSet<Vec<Vertex>> = vertices.iter()
+                    .map(|w| S.iter().filter(|s| match orientation {
+                        Orientation::Out => arcs.contains(&(*w, **s)),
+                        Orientation::In => arcs.contains(&(**s, *w)),
+                    }).cloned().collect())
+                    .collect();
+                assert_eq!(nquery.count_traces(&S), traces.len());
+            }
+        }
+    }
+
+    #[test]
+    fn closed_traces() {
+        let mut graph = EditGraph::from_txt("test1_shattered.txt").expect("File not found.");
+        let graph = DegenGraph::from_graph(&graph);
+        let vertices = graph.vertices().cloned().sorted_unstable().collect_vec();
+
+        for r in 1..=2 {
+            let mut nquery = NQuery::new(&graph);
+            nquery.set_radius(r);
+            nquery.set_closed(true);
+            nquery.ensure_size(3);
+
+            for S in vertices.iter().cloned().combinations(3) {
+                let traces:BTreeSet<Vec<Vertex>> = vertices.iter()
+                    .map(|v| {
+                        let ball = nquery.ball(v);
+                        S.iter().filter(|s| ball.contains(s)).cloned().collect()
+                    })
+                    .collect();
+                assert_eq!(nquery.count_traces(&S), traces.len());
+            }
+        }
+    }
+
+    #[test]
+    fn distance_traces() {
+        let mut graph = EditGraph::from_txt("test1_shattered.txt").expect("File not found.");
+        let graph = DegenGraph::from_graph(&graph);
+        let vertices = graph.vertices().cloned().sorted_unstable().collect_vec();
+
+        for r in 2..=3 {
+            let mut nquery = NQuery::new(&graph);
+            nquery.set_radius(r);
+
+            // Compute balls by breadth-first search
+            let mut balls:VertexMap<BTreeSet<Vertex>> = VertexMap::default();
+            for v in vertices.iter() {
+                let mut ball:BTreeSet<Vertex> = BTreeSet::default();
+                let mut frontier = vec![*v];
+                ball.insert(*v);
+                for _ in 0..r {
+                    let mut next = Vec::default();
+                    for u in frontier {
+                        for x in graph.neighbours(&u) {
+                            if ball.insert(*x) {
+                                next.push(*x);
+                            }
+                        }
+                    }
+                    frontier = next;
+                }
+                ball.remove(v);
+                balls.insert(*v, ball);
+            }
+
+            for S in vertices.iter().cloned().combinations(3) {
+                let traces:BTreeSet<Vec<Vertex>> = vertices.iter()
+                    .map(|v| S.iter().filter(|s| balls[v].contains(s)).cloned().collect())
+                    .collect();
+                assert_eq!(nquery.count_traces(&S), traces.len());
+            }
+        }
+    }
+
     #[test]
     fn bad() {
         let k = 3;
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+
+use graphbench::graph::*;
+use graphbench::degengraph::DegenGraph;
+
+/// Returns the vertices of `graph` in an order in which every vertex appears after all
+/// of its left neighbours. Any such order has the same left neighbourhoods as `graph`.
+pub fn linear_ordering(graph:&DegenGraph) -> Vec<Vertex> {
+    let mut missing:VertexMap<usize> = graph.vertices().map(|v| (*v, graph.left_neighbours_slice(v).len())).collect();
+    let mut queue:VecDeque<Vertex> = graph.vertices().filter(|v| missing[v] == 0).cloned().collect();
+    let mut res = Vec::with_capacity(graph.num_vertices());
+
+    while let Some(v) = queue.pop_front() {
+        res.push(v);
+        for u in graph.neighbours(&v) {
+            // Every right neighbour u of v has v as a left neighbour
+            if graph.left_neighbours_slice(u).contains(&v) {
+                let count = missing.get_mut(u).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    queue.push_back(*u);
+                }
+            }
+        }
+    }
+
+    assert_eq!(res.len(), graph.num_vertices());
+    res
+}
+
+/// Pairs (w, dist) of vertices reachable from some vertex together with their distance, for every vertex.
+pub type ReachabilityMap = VertexMap<Vec<(Vertex, u32)>>;
+
+/// Computes the weakly `r`-reachable sets with respect to `order`. A vertex w is weakly r-reachable
+/// from u if there is a path of length at most r from u to w on which w is the smallest vertex.
+///
+/// Returns two maps: the first one contains for every vertex u the pairs (w, dist) where w is weakly
+/// r-reachable from u and dist is the length of a shortest such path, the second one contains
+/// the same pairs indexed by w. Both include u itself with distance zero.
+pub fn weak_reachability(graph:&DegenGraph, order:&[Vertex], r:usize) -> (ReachabilityMap, ReachabilityMap) {
+    let index:VertexMap<usize> = order.iter().enumerate().map(|(i,v)| (*v, i)).collect();
+    let mut wreach:ReachabilityMap = VertexMap::default();
+    let mut wreach_inv:ReachabilityMap = VertexMap::default();
+
+    for w in order {
+        // BFS from w which only visits vertices to the right of w
+        let mut dist:VertexMap<u32> = VertexMap::default();
+        let mut queue:VecDeque<Vertex> = VecDeque::default();
+        dist.insert(*w, 0);
+        queue.push_back(*w);
+
+        while let Some(u) = queue.pop_front() {
+            let d = dist[&u];
+            if d as usize == r {
+                continue;
+            }
+            for x in graph.neighbours(&u) {
+                if index[x] > index[w] && !dist.contains_key(x) {
+                    dist.insert(*x, d+1);
+                    queue.push_back(*x);
+                }
+            }
+        }
+
+        for (u, d) in dist.iter() {
+            wreach.entry(*u).or_default().push((*w, *d));
+        }
+        wreach_inv.insert(*w, dist.into_iter().collect());
+    }
+
+    (wreach, wreach_inv)
+}
+
+/// Computes the weak and the strong `r`-colouring number of `order`, that is, the largest number of
+/// vertices which are weakly (strongly) r-reachable from a single vertex, including the vertex itself.
+/// A vertex w is strongly r-reachable from u if there is a path of length at most r from u to w on
+/// which w is the only vertex to the left of u.
+pub fn colouring_numbers(graph:&DegenGraph, order:&[Vertex], r:usize) -> (usize, usize) {
+    let (wreach, _) = weak_reachability(graph, order, r);
+    let wcol = wreach.values().map(|W| W.len()).max().unwrap_or(0);
+
+    let index:VertexMap<usize> = order.iter().enumerate().map(|(i,v)| (*v, i)).collect();
+    let mut col = 0;
+    for u in order {
+        // BFS from u whose inner vertices lie to the right of u
+        let mut dist:VertexMap<usize> = VertexMap::default();
+        let mut queue:VecDeque<Vertex> = VecDeque::default();
+        let mut sreach = 1;
+        dist.insert(*u, 0);
+        queue.push_back(*u);
+
+        while let Some(y) = queue.pop_front() {
+            let d = dist[&y];
+            if d == r {
+                continue;
+            }
+            for x in graph.neighbours(&y) {
+                if dist.contains_key(x) {
+                    continue;
+                }
+                dist.insert(*x, d+1);
+                if index[x] < index[u] {
+                    sreach += 1;
+                } else {
+                    queue.push_back(*x);
+                }
+            }
+        }
+        col = std::cmp::max(col, sreach);
+    }
+
+    (wcol, col)
+}
+
+/// Returns the vertices sorted by decreasing degree, ties are broken by the vertex id.
+pub fn degree_ordering<G:Graph>(graph:&G) -> Vec<Vertex> {
+    let mut order:Vec<Vertex> = graph.vertices().cloned().collect();
+    order.sort_by_key(|v| (std::cmp::Reverse(graph.degree(v)), *v));
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphbench::{editgraph::EditGraph, graph::MutableGraph};
+    use itertools::Itertools;
+
+    #[test]
+    fn path() {
+        let mut G = EditGraph::new();
+        for u in 0..4 {
+            G.add_edge(&u, &(u+1));
+        }
+        let order = (0..5).collect_vec();
+        let D = DegenGraph::with_ordering(&G, order.iter());
+
+        assert_eq!(colouring_numbers(&D, &order, 1), (2, 2));
+        // 4 weakly reaches 3 and 2, but strongly only 3
+        assert_eq!(colouring_numbers(&D, &order, 2), (3, 2));
+        assert_eq!(colouring_numbers(&D, &order, 4), (5, 2));
+    }
+
+    #[test]
+    fn star() {
+        let mut G = EditGraph::new();
+        for v in 1..5 {
+            G.add_edge(&0, &v);
+        }
+
+        // With the centre first every leaf only reaches the centre
+        let order = degree_ordering(&G);
+        assert_eq!(order, vec![0, 1, 2, 3, 4]);
+        let D = DegenGraph::with_ordering(&G, order.iter());
+        assert_eq!(colouring_numbers(&D, &order, 1), (2, 2));
+        assert_eq!(colouring_numbers(&D, &order, 2), (2, 2));
+
+        // With the centre last it reaches all leaves
+        let order = vec![1, 2, 3, 4, 0];
+        let D = DegenGraph::with_ordering(&G, order.iter());
+        assert_eq!(colouring_numbers(&D, &order, 1), (5, 5));
+        assert_eq!(colouring_numbers(&D, &order, 2), (5, 5));
+    }
+}
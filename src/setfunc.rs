@@ -197,6 +197,19 @@ impl SmallSetFunc {
         self.is_at_least(&universe, &(self.size() as i32))
     }    
 
+    pub fn contains_induced_matching(&self) -> bool {
+        let mut it:u128 = (1 << self.size()) - 1;
+        while it != 0 { // Iterates over all singletons
+            let ix = u128::trailing_zeros(it);
+            let singleton = 1 << ix;
+            it ^= singleton;
+            if self.is_zero(&singleton) {
+                return false
+            }
+        }
+        true
+    }
+
     pub fn is_ladder(&self) -> bool {
         if self.size() == 0 {
             return true;
@@ -232,6 +245,47 @@ impl SmallSetFunc {
         return false
     }
 
+    pub fn is_semi_ladder(&self) -> bool {
+        if self.size() == 0 {
+            return true;
+        }
+
+        // Witnesses of a semi-ladder have pairwise distinct traces
+        if self.count_nonzero() < self.size() {
+            return false;
+        }
+
+        let universe = (1 << self.size()) - 1;
+        debug_assert_eq!(universe, self.convert_set(&self.universe));
+
+        self.is_semi_ladder_rec(universe, self.size())
+    }
+
+    /// Tests whether the elements in `bitset` can be ordered as s_1, ..., s_k such that
+    /// for every i there is a vertex which sees s_{i+1}, ..., s_k but not s_i. Elements
+    /// outside of `bitset` (the ones placed earlier) are ignored.
+    fn is_semi_ladder_rec(&self, bitset:u128, size:usize) -> bool {
+        if size == 0 {
+            return true;
+        }
+
+        let mut it = bitset;
+        while it != 0 { // Iterates over all ones in `bitset`
+            let ix = u128::trailing_zeros(it);
+            it ^= 1 << ix;
+            let rest = bitset & !(1 << ix);
+            if self.has_restricted_trace(&bitset, &rest) && self.is_semi_ladder_rec(rest, size-1) {
+                return true
+            }
+        }
+        false
+    }
+
+    /// Tests whether some nonzero entry X satisfies X ∩ mask = target
+    fn has_restricted_trace(&self, mask:&u128, target:&u128) -> bool {
+        self.values.iter().any(|(bitset, count)| *count > 0 && (*bitset & *mask) == *target)
+    }
+
     #[inline]
     fn is_nonzero(&self, bitset:&u128) -> bool {
         self.values.get(bitset).map_or(false, |count| count > &0)
@@ -437,4 +491,32 @@ mod tests {
         f[&vec![      2]] = 1;
         assert!(f.is_ladder());        
     }
+
+    #[test]
+    fn test_induced_matching() {
+        let mut f:SmallSetFunc = SmallSetFunc::new(&vec![0,1,2]);
+
+        assert!(!f.contains_induced_matching());
+        f[&vec![0    ]] = 1;
+        f[&vec![  1  ]] = 2;
+        f[&vec![0,1,2]] = 4;
+        assert!(!f.contains_induced_matching());
+        f[&vec![    2]] = 1;
+        assert!(f.contains_induced_matching());
+    }
+
+    #[test]
+    fn test_semi_ladder() {
+        let mut f:SmallSetFunc = SmallSetFunc::new(&vec![0,1,2]);
+
+        assert!(!f.is_semi_ladder());
+        f[&vec![  1,2]] = 1;
+        f[&vec![0,  2]] = 1;
+        f[&vec![    2]] = 5;
+        assert!(!f.is_semi_ladder());
+        assert!(!f.is_ladder());
+        f[&vec![]] = 1;
+        assert!(f.is_semi_ladder());
+        assert!(!f.is_ladder());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,21 @@
+use itertools::Itertools;
+
+use graphbench::editgraph::EditGraph;
+use graphbench::graph::{Vertex, MutableGraph};
+
+/// Vertices 0..k and one vertex for every subset of them, adjacent to exactly that subset.
+/// Returns the graph and the first vertex after the subset vertices, the last subset is the full set.
+/// Ordering the vertices by name puts every subset into the left neighbourhood of its vertex.
+pub fn powerset_graph(k:Vertex) -> (EditGraph, Vertex) {
+    let mut G = EditGraph::new();
+    G.add_vertices(0..k);
+    let mut v = k;
+    for set in (0..k).powerset() {
+        G.add_vertex(&v);
+        for u in set {
+            G.add_edge(&u, &v);
+        }
+        v += 1;
+    }
+    (G, v)
+}
@@ -0,0 +1,105 @@
+use fxhash::FxHashMap;
+
+use graphbench::graph::*;
+use graphbench::editgraph::EditGraph;
+
+use itertools::*;
+
+/// Partition of the vertices of a graph into twin classes. False twins have the same open
+/// neighbourhood, true twins the same closed neighbourhood. Twins have the same set in the
+/// respective set system and the same trace on every set not containing them, so they can
+/// never both be part of a shattered set and one of them suffices as a witness.
+pub struct TwinClasses {
+    representative: VertexMap<Vertex>,
+    members: VertexMap<Vec<Vertex>>,
+}
+
+impl TwinClasses {
+    /// Groups the vertices of `graph` into classes of false twins, or of true twins if `closed` is set.
+    /// The smallest vertex of each class is its representative.
+    pub fn new(graph:&EditGraph, closed:bool) -> Self {
+        let mut classes:FxHashMap<Vec<Vertex>, Vec<Vertex>> = FxHashMap::default();
+        for v in graph.vertices() {
+            let mut N:Vec<Vertex> = graph.neighbours(v).cloned().collect();
+            if closed {
+                N.push(*v);
+            }
+            N.sort_unstable();
+            classes.entry(N).or_default().push(*v);
+        }
+
+        let mut representative = VertexMap::default();
+        let mut members = VertexMap::default();
+        for mut class in classes.into_values() {
+            class.sort_unstable();
+            let rep = class[0];
+            for v in class.iter() {
+                representative.insert(*v, rep);
+            }
+            members.insert(rep, class);
+        }
+
+        TwinClasses{ representative, members }
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.members.len()
+    }
+
+    /// The subgraph induced by the class representatives.
+    pub fn reduce(&self, graph:&EditGraph) -> EditGraph {
+        let mut res = EditGraph::new();
+        for rep in self.members.keys() {
+            res.add_vertex(rep);
+            for u in graph.neighbours(rep) {
+                if self.members.contains_key(u) {
+                    res.add_edge(rep, u);
+                }
+            }
+        }
+        res
+    }
+
+    /// Replaces every vertex of `S` by the representative of its class.
+    pub fn reduce_set(&self, S:&VertexSet) -> VertexSet {
+        S.iter().filter_map(|v| self.representative.get(v)).cloned().collect()
+    }
+
+    /// Number of vertices in every class, indexed by the representative of the class.
+    pub fn multiplicities(&self) -> VertexMap<usize> {
+        self.members.iter().map(|(rep, class)| (*rep, class.len())).collect()
+    }
+
+    /// Returns all vertices of the classes represented by `reps`, sorted.
+    pub fn lift(&self, reps:&[Vertex]) -> Vec<Vertex> {
+        reps.iter().flat_map(|rep| self.members[rep].iter()).cloned().sorted_unstable().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn star() {
+        // Star with centre 0 and three leaves plus a triangle 0,4,5
+        let mut G = EditGraph::new();
+        for v in 1..=5 {
+            G.add_edge(&0, &v);
+        }
+        G.add_edge(&4, &5);
+
+        let twins = TwinClasses::new(&G, false);
+        assert_eq!(twins.num_classes(), 4);
+        assert_eq!(twins.multiplicities()[&1], 3);
+        assert_eq!(twins.lift(&[1, 4]), vec![1, 2, 3, 4]);
+
+        let H = twins.reduce(&G);
+        assert_eq!(H.num_vertices(), 4);
+        assert_eq!(H.num_edges(), 4);
+
+        let twins = TwinClasses::new(&G, true);
+        assert_eq!(twins.num_classes(), 5);
+        assert_eq!(twins.multiplicities()[&4], 2);
+    }
+}